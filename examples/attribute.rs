@@ -0,0 +1,40 @@
+use schema2struct::attr::schema2struct;
+
+#[schema2struct]
+#[derive(Clone, Debug, PartialEq)]
+struct Reading {
+    /// the angle of the sensor, in degrees
+    #[schema(maximum = 90)]
+    angle: u8,
+
+    #[schema(min_length = 1, max_length = 16)]
+    sensor: String,
+
+    label: Option<String>,
+}
+
+#[schema2struct]
+#[derive(Debug)]
+#[serde(rename_all = "camelCase")]
+struct Batch {
+    batch_id: String,
+    readings: Vec<Reading>,
+}
+
+fn main() {
+    let batch: Batch = serde_json::from_str(
+        r#"{ "batchId": "b-1", "readings": [{ "angle": 45, "sensor": "north" }] }"#,
+    )
+    .unwrap();
+
+    // `label` was missing, and it's left out again instead of being `null`
+    assert_eq!(batch.readings[0].label, None);
+    assert_eq!(
+        serde_json::to_string(&batch.readings[0]).unwrap(),
+        r#"{"angle":45,"sensor":"north"}"#
+    );
+
+    assert_eq!(READING_JSON_VALUE["properties"]["angle"]["maximum"], 90);
+
+    println!("{:#}", *BATCH_JSON_VALUE);
+}
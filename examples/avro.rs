@@ -0,0 +1,31 @@
+use schema2struct::schema2struct;
+
+// a `.avsc` file is an Avro schema, it's root record names the struct
+schema2struct!(file = "examples/schemas/sensor_reading.avsc");
+
+fn main() {
+    let reading: SensorReading = serde_json::from_str(
+        r#"{
+            "id": "4f1c2a9e-7d0b-4c8a-9a57-1f0e2b3c4d5e",
+            "takenAt": 1714557600000,
+            "kind": "TEMPERATURE",
+            "value": 21.5,
+            "location": { "lat": 59.33, "lon": 18.06, "label": null },
+            "previous": null,
+            "tags": ["indoor"],
+            "labels": { "floor": "2" },
+            "raw": 215
+        }"#,
+    )
+    .unwrap();
+
+    // a logical type keeps it's underlying type, and a named type is shared by every field using it
+    assert_eq!(reading.taken_at, 1_714_557_600_000);
+    assert!(reading.previous.is_none());
+    assert_eq!(reading.labels["floor"], "2");
+
+    // a union of more than one type is an untagged enum
+    assert!(matches!(reading.raw, Some(SensorReadingRaw::Integer(215))));
+
+    println!("{:#?}", reading);
+}
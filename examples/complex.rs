@@ -5,8 +5,12 @@ schema2struct! {
     struct: Hello,
     enum: ["hello", 12, true],
     default: "hello",
-    required: ["info", "anything", "sdc"],
+    required: ["info", "anything", "sdc", "version"],
     properties: {
+        "version": {
+            type: string,
+            const: "v2"
+        },
         "sdc": {
             type: object,
             struct: key
@@ -19,7 +23,7 @@ schema2struct! {
 
         "anything": {
             type: string,
-            min_length: 52,
+            min_length: 2,
             max_length: 32,
             pattern: r#"a-zA-Z"#,
             format: email,
@@ -31,6 +35,7 @@ schema2struct! {
 fn main() {
     let api_response_from_the_scehma = r#"
         {
+            "version": "v2",
             "info": 23,
             "anything": "hello",
             "sdc": {}
@@ -0,0 +1,44 @@
+use schema2struct::HasJsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// where the user lives
+#[derive(Deserialize, Serialize, Debug, HasJsonSchema)]
+struct Address {
+    city: String,
+
+    #[schema(pattern = "^[0-9]{5}$")]
+    zip: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, HasJsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct User {
+    /// the name shown on the profile
+    #[schema(min_length = 3, max_length = 20)]
+    display_name: String,
+
+    #[schema(format = "email")]
+    email: String,
+
+    age: u8,
+    address: Option<Address>,
+}
+
+fn main() {
+    let schema = User::json_schema();
+
+    assert_eq!(schema["properties"]["displayName"]["minLength"], 3);
+    assert_eq!(schema["properties"]["email"]["format"], "email");
+    assert_eq!(
+        schema["required"],
+        serde_json::json!(["displayName", "email", "age"])
+    );
+
+    // the schema of `Address` is embedded
+    assert_eq!(
+        schema["properties"]["address"]["anyOf"][0]["properties"]["zip"]["pattern"],
+        "^[0-9]{5}$"
+    );
+
+    println!("{:#}", *USER_JSON_VALUE);
+}
@@ -0,0 +1,30 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: Tag,
+    type: object,
+    derive: [PartialEq, Eq, Hash],
+    attrs: [#[non_exhaustive]],
+    properties: {
+        "name": { type: string },
+        "color": {
+            type: string,
+            attrs: [#[serde(default)]]
+        },
+        // `f64` has no `Eq` nor `Hash`, so `TagScore` and `Tag` only get `PartialEq`
+        "score": {
+            type: object,
+            properties: {
+                "value": { type: number }
+            }
+        }
+    }
+}
+
+fn main() {
+    let tag: Tag = serde_json::from_str(r#"{ "name": "rust", "score": { "value": 1 } }"#).unwrap();
+
+    assert_eq!(tag.score, TagScore { value: 1.0 });
+
+    println!("{:#?}", tag);
+}
@@ -0,0 +1,29 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: Order,
+    derive: [PartialEq],
+    from_example: [
+        r#"{ "id": 1, "total": 9, "items": [{ "sku": "a-1", "qty": 2 }], "coupon": null }"#,
+        r#"{ "id": 2, "total": 4.5, "items": [], "coupon": "SAVE10", "gift": true }"#
+    ]
+}
+
+fn main() {
+    let order: Order = serde_json::from_str(
+        r#"{ "id": 3, "total": 12.5, "items": [{ "sku": "b-2", "qty": 1 }], "coupon": null }"#,
+    )
+    .unwrap();
+
+    // `gift` was missing from one of the examples, so it can be left out
+    assert_eq!(order.gift, None);
+    assert_eq!(
+        order.items[0],
+        OrderItems {
+            sku: "b-2".to_owned(),
+            qty: 1
+        }
+    );
+
+    println!("{:#?}", order);
+}
@@ -0,0 +1,35 @@
+use schema2struct::schema2struct;
+
+// a `*.jtd.json` file is a JSON Type Definition, it's named after the file
+schema2struct!(file = "examples/schemas/event.jtd.json");
+
+fn main() {
+    let event: Event = serde_json::from_str(
+        r#"{
+            "id": "e-1",
+            "at": "2024-05-01T10:00:00Z",
+            "retries": 0,
+            "priority": "HIGH",
+            "actor": { "id": "u-1", "name": "Sam" },
+            "counters": { "clicks": 3 },
+            "tags": ["signup"],
+            "payload": { "kind": "USER_DELETED", "userId": "u-2", "reason": null },
+            "extra": { "anything": [1, 2] }
+        }"#,
+    )
+    .unwrap();
+
+    // `values` is a map, and a `ref` keeps the name of it's definition
+    assert_eq!(event.counters["clicks"], 3);
+    assert_eq!(event.actor.name, "Sam");
+
+    // the `discriminator` tags the enum, every variant is named after it's tag
+    assert!(matches!(
+        &event.payload,
+        EventPayload::UserDeleted(deleted) if deleted.user_id == "u-2"
+    ));
+
+    assert_eq!(event.trace_id, None);
+
+    println!("{:#?}", event);
+}
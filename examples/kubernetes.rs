@@ -0,0 +1,41 @@
+use schema2struct::schema2struct;
+
+// the storage version is imported if there's no `version`
+schema2struct!(crd = "examples/kubernetes/crontab.yaml", version = "v1");
+
+fn main() {
+    let spec: CronTabSpec = serde_json::from_str(
+        r#"{
+            "cronSpec": "*/5 * * * *",
+            "image": "busybox",
+            "maxSurge": "25%",
+            "maxUnavailable": 1,
+            "labels": { "team": "platform" },
+            "template": { "containers": [{ "name": "job" }] },
+            "ports": [{ "port": "http" }, { "port": 8080 }]
+        }"#,
+    )
+    .unwrap();
+
+    // every `x-kubernetes-int-or-string` is the same `IntOrString`
+    assert!(matches!(spec.max_surge, Some(IntOrString::String(ref surge)) if surge == "25%"));
+    assert!(matches!(spec.max_unavailable, Some(IntOrString::Int(1))));
+    assert!(matches!(
+        spec.ports.as_deref().unwrap()[1].port,
+        IntOrString::Int(8080)
+    ));
+
+    // a map keeps the type of it's values, and the fields it preserves are kept as they are
+    assert_eq!(spec.labels.as_ref().unwrap()["team"], "platform");
+    assert_eq!(
+        spec.template.as_ref().unwrap()["containers"][0]["name"],
+        "job"
+    );
+
+    let status = CronTabStatus {
+        replicas: Some(2),
+        last_schedule_time: None,
+    };
+
+    println!("{:#?}\n{:#?}", spec, status);
+}
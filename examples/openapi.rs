@@ -0,0 +1,39 @@
+use schema2struct::schema2struct;
+
+// the path is relative to the crate's `Cargo.toml`
+schema2struct!(
+    openapi = "examples/openapi/petstore.yaml",
+    components = ["Owner", "Pet"],
+    derive = [PartialEq]
+);
+
+fn main() {
+    let owner: Owner = serde_json::from_str(
+        r#"{
+            "id": 1,
+            "name": "Sam",
+            "address": { "city": "Riyadh", "zip": null },
+            "pets": [
+                { "petType": "cat", "name": "Tom" },
+                { "petType": "dog", "name": "Rex", "weight": 12.5 }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    // `Address` is referenced, so it keeps the name of it's component
+    assert_eq!(owner.address.zip, None);
+
+    // `Pet` is a `oneOf` with a discriminator, so it's an enum tagged by `petType`
+    assert_eq!(
+        owner.pets.as_deref().unwrap()[0],
+        Pet::Cat(PetCat {
+            name: "Tom".to_owned(),
+            indoor: None
+        })
+    );
+
+    assert!(serde_json::from_str::<Pet>(r#"{ "petType": "cow", "name": "Bella" }"#).is_err());
+
+    println!("{:#?}", owner);
+}
@@ -0,0 +1,66 @@
+use schema2struct::{schema2struct, Operation};
+
+// every operation brings the components it uses, like `Owner` and `Problem`
+schema2struct!(
+    openapi = "examples/openapi/petstore.yaml",
+    operations = ["getOwner", "createOwner", "listPets"]
+);
+
+// a generic client only needs the `Operation` of each request
+fn url<O: Operation>(base: &str) -> String {
+    format!("{}{}", base, O::PATH)
+}
+
+fn main() {
+    assert_eq!(GetOwner::METHOD, "GET");
+    assert_eq!(
+        url::<GetOwner>("https://pets.example"),
+        "https://pets.example/owners/{ownerId}"
+    );
+
+    let params = GetOwnerPathParams { owner_id: 7 };
+    assert_eq!(serde_json::to_value(&params).unwrap()["ownerId"], 7);
+
+    // the responses are decoded by their status
+    let response = GetOwner::decode_response(
+        200,
+        br#"{ "id": 7, "name": "Sam", "address": { "city": "Riyadh" } }"#,
+    )
+    .unwrap();
+    assert!(matches!(&response, GetOwnerResponse::Ok(owner) if owner.name == "Sam"));
+    assert_eq!(response.status(), 200);
+
+    assert!(matches!(
+        GetOwnerResponse::from_response(404, b"").unwrap(),
+        GetOwnerResponse::NotFound
+    ));
+
+    // `default` keeps the status it got
+    let response =
+        GetOwnerResponse::from_response(500, br#"{ "message": "try again later" }"#).unwrap();
+    assert_eq!(response.status(), 500);
+
+    // `listPets` has no `default`, so an undeclared status is an error
+    assert!(matches!(
+        ListPetsResponse::from_response(500, b""),
+        Err(schema2struct::ResponseError::UnexpectedStatus(500))
+    ));
+
+    let query = ListPetsQueryParams {
+        limit: Some(10),
+        species: None,
+    };
+    println!("{}", serde_json::to_string(&query).unwrap());
+
+    let request: CreateOwnerRequest = Owner {
+        id: 1,
+        name: "Sam".to_owned(),
+        email: None,
+        address: Address {
+            city: "Riyadh".to_owned(),
+            zip: None,
+        },
+        pets: None,
+    };
+    println!("{:#?}", request);
+}
@@ -0,0 +1,19 @@
+use schema2struct::schema2struct;
+
+// the paths are relative to the crate's `Cargo.toml`, and the format comes from the extension
+schema2struct!(file = "examples/schemas/server.yaml", derive = [PartialEq]);
+schema2struct!(file = "examples/schemas/database.toml");
+
+fn main() {
+    let server: Server =
+        serde_json::from_str(r#"{ "host": "localhost", "port": 8080, "proxy": null }"#).unwrap();
+
+    assert_eq!(server.port, 8080);
+    assert_eq!(server.proxy, None);
+
+    let database: Database =
+        serde_json::from_str(r#"{ "url": "postgres://localhost", "pool": { "size": 4 } }"#)
+            .unwrap();
+
+    println!("{:#?}\n{:#?}", server, database);
+}
@@ -0,0 +1,24 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: User,
+    type: object,
+    description: "a registered user",
+    properties: {
+        "email": { type: string, format: email, min_length: 3 },
+        "address": {
+            type: object,
+            properties: {
+                "city": { type: string }
+            }
+        }
+    },
+    required: ["email", "address"]
+}
+
+// the constraints and formats come from the schema, not from the fields
+fn main() {
+    let schema = schema2struct::schemars::schema_for!(User);
+
+    println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+}
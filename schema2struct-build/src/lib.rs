@@ -0,0 +1,480 @@
+//! # schema2struct-build
+//!
+//! Generates the `schema2struct!` types from JSON Schema files in a `build.rs`,
+//! so the code can be read as is and rust-analyzer doesn't have to expand the macro
+//!
+//! the files can be written in JSON, YAML or TOML, they're read by their extension, a `.avsc` file
+//! is an Avro schema
+//!
+//! # Example
+//! ```ignore
+//! // build.rs
+//! fn main() {
+//!     schema2struct_build::Builder::new()
+//!         .schema_dir("schemas")
+//!         .out_file("types.rs")
+//!         .generate()
+//!         .unwrap();
+//! }
+//!
+//! // src/lib.rs
+//! include!(concat!(env!("OUT_DIR"), "/types.rs"));
+//! ```
+//!
+//! the TypeScript declarations of the same schemas can be written along with them, for a frontend
+//! reading the same payloads
+//!
+//! ```ignore
+//! schema2struct_build::Builder::new()
+//!     .schema_dir("schemas")
+//!     .typescript_file("web/src/types.d.ts")
+//!     .generate()
+//!     .unwrap();
+//! ```
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use schema2struct_core::{
+    generate_all_indexed, read_schema_file, typescript::generate_declarations, FileError, Options,
+};
+
+/// the extensions of the files picked up from a schema directory
+const SCHEMA_EXTENSIONS: [&str; 5] = ["json", "yaml", "yml", "toml", "avsc"];
+
+/// An error from generating the types
+#[derive(Debug)]
+pub enum Error {
+    /// a schema file couldn't be read or parsed
+    File(FileError),
+
+    /// a schema was read, but the code couldn't be generated from it
+    Schema { path: PathBuf, error: syn::Error },
+
+    /// two schema files generate a root type with the same name
+    Clash {
+        name: String,
+        first: PathBuf,
+        second: PathBuf,
+    },
+
+    /// a directory couldn't be walked or the output couldn't be written
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+
+    /// `OUT_DIR` isn't set, which means it's not running from a build script
+    OutDir,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::File(e) => e.fmt(f),
+            Error::Schema { path, error } => write!(f, "{}: {}", path.display(), error),
+            Error::Clash {
+                name,
+                first,
+                second,
+            } => write!(
+                f,
+                "both {} and {} generate the type `{}`, give one of them a different `title`",
+                first.display(),
+                second.display(),
+                name
+            ),
+            Error::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            Error::OutDir => f.write_str(
+                "`OUT_DIR` is not set, use `out_dir` when running outside of a build script",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<FileError> for Error {
+    fn from(e: FileError) -> Self {
+        Error::File(e)
+    }
+}
+
+/// Configures and runs the generation
+pub struct Builder {
+    schema_dirs: Vec<PathBuf>,
+    schema_files: Vec<PathBuf>,
+    out_dir: Option<PathBuf>,
+    out_file: PathBuf,
+    typescript_file: Option<PathBuf>,
+    options: Options,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    pub fn new() -> Self {
+        Self {
+            schema_dirs: Vec::new(),
+            schema_files: Vec::new(),
+            out_dir: None,
+            out_file: PathBuf::from("schema2struct.rs"),
+            typescript_file: None,
+            options: Options::default(),
+        }
+    }
+
+    /// Adds every `.json`, `.yaml`, `.yml` and `.toml` schema in a directory, and it's sub
+    /// directories, a `.jtd.json` file is read as a JSON Type Definition, and a `.avsc` file as an
+    /// Avro schema
+    pub fn schema_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.schema_dirs.push(dir.as_ref().to_owned());
+        self
+    }
+
+    /// Adds a single schema file
+    pub fn schema_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.schema_files.push(file.as_ref().to_owned());
+        self
+    }
+
+    /// The directory to write to, `OUT_DIR` by default
+    pub fn out_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.out_dir = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    /// The file name to write to inside the output directory, `schema2struct.rs` by default
+    pub fn out_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.out_file = file.as_ref().to_owned();
+        self
+    }
+
+    /// Also writes the TypeScript declarations of every schema to a `.d.ts` file, the path isn't
+    /// inside the output directory, so it's relative to the crate in a build script
+    pub fn typescript_file(mut self, file: impl AsRef<Path>) -> Self {
+        self.typescript_file = Some(file.as_ref().to_owned());
+        self
+    }
+
+    /// The generation options for every schema
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Generates the types of every schema into a single formatted file
+    ///
+    /// # Returns
+    /// The path of the written Rust file
+    ///
+    /// the schemas are generated together, so a type they share, like the same nested object, is
+    /// only generated once
+    ///
+    /// # Errors
+    /// - A schema can't be read or generated
+    /// - Two schemas generate different types with the same name
+    /// - The file can't be written
+    pub fn generate(self) -> Result<PathBuf, Error> {
+        let out_dir = match self.out_dir {
+            Some(out_dir) => out_dir,
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or(Error::OutDir)?,
+        };
+
+        let mut schema_files = self.schema_files;
+
+        for dir in &self.schema_dirs {
+            // a new file in the directory should also trigger a rebuild
+            println!("cargo:rerun-if-changed={}", dir.display());
+
+            schema_files.extend(schema_files_in(dir)?);
+        }
+
+        let mut schemas = Vec::new();
+
+        // the types are generated together, so a type two files share is only generated once, but
+        // two roots can't have the same name
+        let mut roots: HashMap<String, &PathBuf> = HashMap::new();
+
+        for path in &schema_files {
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            let schema = read_schema_file(path)?;
+
+            // the name of it's root type, from it's `title` or it's file name
+            let name = schema
+                .struct_name
+                .clone()
+                .expect("a schema file is always named");
+
+            if let Some(first) = roots.insert(name.clone(), path) {
+                return Err(Error::Clash {
+                    name,
+                    first: first.clone(),
+                    second: path.clone(),
+                });
+            }
+
+            schemas.push(schema);
+        }
+
+        // the error is reported on the first file it shows up with, a type that clashes with one
+        // from an earlier file is reported on the later one
+        let output = generate_all_indexed(&schemas, &self.options).map_err(|(index, error)| {
+            Error::Schema {
+                path: schema_files[index].clone(),
+                error,
+            }
+        })?;
+
+        let code = format_code(output).map_err(|error| Error::Schema {
+            path: out_dir.join(&self.out_file),
+            error,
+        })?;
+
+        let out_path = out_dir.join(&self.out_file);
+
+        std::fs::write(&out_path, code).map_err(|error| Error::Io {
+            path: out_path.clone(),
+            error,
+        })?;
+
+        if let Some(typescript_file) = &self.typescript_file {
+            let declarations = generate_declarations(&schemas, &self.options);
+
+            std::fs::write(typescript_file, declarations).map_err(|error| Error::Io {
+                path: typescript_file.clone(),
+                error,
+            })?;
+        }
+
+        Ok(out_path)
+    }
+}
+
+/// The start of every generated file, a file without it is written by hand
+pub const GENERATED_HEADER: &str = "// @generated by schema2struct";
+
+/// Formats the generated code, with a header saying where it came from
+pub fn format_code(tokens: proc_macro2::TokenStream) -> syn::Result<String> {
+    let file = syn::parse2::<syn::File>(tokens)?;
+
+    Ok(format!(
+        "{}, do not edit by hand\n\n{}",
+        GENERATED_HEADER,
+        prettyplease::unparse(&file)
+    ))
+}
+
+/// Finds every schema file in a directory, and it's sub directories
+///
+/// The files are sorted, so the output is always the same
+///
+/// # Errors
+/// The directory, or one of it's sub directories, can't be read
+pub fn schema_files_in(dir: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut files = Vec::new();
+    collect_schema_files(dir, &mut files)?;
+
+    Ok(files)
+}
+
+fn collect_schema_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let io_error = |error| Error::Io {
+        path: dir.to_owned(),
+        error,
+    };
+
+    let mut entries = std::fs::read_dir(dir)
+        .map_err(io_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(io_error)?;
+
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            collect_schema_files(&path, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| SCHEMA_EXTENSIONS.iter().any(|e| extension == *e))
+        {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a fresh directory for every test, they run in parallel
+    fn temp_dir(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "schema2struct-build-{}-{}",
+            std::process::id(),
+            name
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+
+        for (file, content) in files {
+            let path = dir.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        dir
+    }
+
+    fn generate(dir: &Path) -> Result<PathBuf, Error> {
+        Builder::new()
+            .schema_dir(dir.join("schemas"))
+            .out_dir(dir)
+            .generate()
+    }
+
+    #[test]
+    fn schema_files_are_sorted() {
+        let dir = temp_dir(
+            "sorted",
+            &[
+                ("b.json", "{}"),
+                ("sub/c.toml", ""),
+                ("a.yaml", ""),
+                ("a.jtd.json", "{}"),
+                ("notes.txt", ""),
+                ("sensor.avsc", "{}"),
+            ],
+        );
+
+        let files = schema_files_in(&dir).unwrap();
+
+        assert_eq!(
+            files,
+            [
+                "a.jtd.json",
+                "a.yaml",
+                "b.json",
+                "sensor.avsc",
+                "sub/c.toml"
+            ]
+            .map(|file| dir.join(file))
+        );
+    }
+
+    #[test]
+    fn generates_every_schema() {
+        let dir = temp_dir(
+            "generate",
+            &[
+                (
+                    "schemas/user.json",
+                    r#"{ "type": "object", "properties": { "name": { "type": "string" } } }"#,
+                ),
+                ("schemas/order.yaml", "title: Order\ntype: object\n"),
+            ],
+        );
+
+        let code = std::fs::read_to_string(generate(&dir).unwrap()).unwrap();
+
+        assert!(code.starts_with(GENERATED_HEADER));
+        assert!(code.contains("pub struct Order"));
+        assert!(code.contains("pub struct User"));
+    }
+
+    #[test]
+    fn untitled_roots_clash_by_their_file_names() {
+        let dir = temp_dir(
+            "clash",
+            &[
+                ("schemas/a/user.json", r#"{ "type": "object" }"#),
+                ("schemas/b/user.yaml", "type: object\n"),
+            ],
+        );
+
+        let error = generate(&dir).unwrap_err();
+
+        assert!(matches!(
+            &error,
+            Error::Clash { name, first, second }
+                if name == "User"
+                    && *first == dir.join("schemas/a/user.json")
+                    && *second == dir.join("schemas/b/user.yaml")
+        ));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "both {} and {} generate the type `User`, give one of them a different `title`",
+                dir.join("schemas/a/user.json").display(),
+                dir.join("schemas/b/user.yaml").display()
+            )
+        );
+    }
+
+    #[test]
+    fn schema_error_is_reported_on_the_file() {
+        // `UserAddress` is nested in the first file, the second one is a different type
+        let dir = temp_dir(
+            "schema",
+            &[
+                (
+                    "schemas/a.json",
+                    r#"{
+                        "title": "User",
+                        "type": "object",
+                        "properties": {
+                            "address": { "type": "object", "properties": { "zip": { "type": "string" } } }
+                        }
+                    }"#,
+                ),
+                (
+                    "schemas/b.json",
+                    r#"{ "title": "UserAddress", "type": "object", "properties": { "line": { "type": "string" } } }"#,
+                ),
+                (
+                    "schemas/c.json",
+                    r#"{ "title": "Order", "type": "object" }"#,
+                ),
+            ],
+        );
+
+        let error = generate(&dir).unwrap_err();
+
+        assert!(matches!(
+            &error,
+            Error::Schema { path, .. } if *path == dir.join("schemas/b.json")
+        ));
+
+        // the first file with an error is the one it's reported on, even if a later one has one too
+        let dir = temp_dir(
+            "first",
+            &[
+                ("schemas/a.json", r#"{ "type": "object" }"#),
+                (
+                    "schemas/b.json",
+                    r#"{ "type": "object", "properties": { "id": { "const": [1] } } }"#,
+                ),
+                (
+                    "schemas/c.json",
+                    r#"{ "type": "object", "properties": { "id": { "const": [2] } } }"#,
+                ),
+            ],
+        );
+
+        assert!(matches!(
+            generate(&dir).unwrap_err(),
+            Error::Schema { path, .. } if path == dir.join("schemas/b.json")
+        ));
+    }
+}
@@ -0,0 +1,602 @@
+//! # schema2struct
+//!
+//! Generates a formatted Rust module for every JSON Schema file, so the types
+//! can be checked in and reviewed like any other code
+//!
+//! # Example
+//! ```sh
+//! # writes `src/schemas/user.rs`, `src/schemas/order.rs` and `src/schemas/mod.rs`
+//! schema2struct schemas/ --out-dir src/schemas --derive PartialEq
+//!
+//! # in CI, fails if the checked in modules are stale
+//! schema2struct schemas/ --out-dir src/schemas --derive PartialEq --check
+//!
+//! # along with the TypeScript declarations of the same schemas, for the frontend
+//! schema2struct schemas/ --out-dir src/schemas --typescript web/src/schemas.d.ts
+//!
+//! # the components of an OpenAPI document, in a single module
+//! schema2struct openapi api.yaml --component User --component Order --out src/api.rs
+//!
+//! # the types of some operations, along with the components they use
+//! schema2struct openapi api.yaml --operation getUser --operation createUser --out src/api.rs
+//!
+//! # the `spec` and `status` of a Kubernetes custom resource
+//! schema2struct crd deploy/crontab.yaml --version v1 --out src/crontab.rs
+//!
+//! # the Avro schema of a JSON Schema, for the consumers on the JVM
+//! schema2struct avro schemas/order.yaml --namespace com.example --out avro/order.avsc
+//! ```
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::Parser as _;
+use quote::quote;
+use schema2struct_build::{format_code, schema_files_in, GENERATED_HEADER};
+use schema2struct_core::{
+    avro, generate, generate_all,
+    infer::parse_examples,
+    infer_schema,
+    kubernetes::CustomResourceDefinition,
+    models::RenameAll,
+    naming::{field_ident, type_ident},
+    openapi::{generate_operations, OpenApiDocument},
+    read_schema_file,
+    typescript::generate_declarations,
+    JsonSchema, Options,
+};
+use syn::parse::Parser as _;
+
+/// the name of the module that declares every generated module
+const MOD_FILE: &str = "mod.rs";
+
+/// Generates Rust modules from JSON Schema files
+#[derive(clap::Parser)]
+#[command(
+    name = "schema2struct",
+    version,
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    #[command(flatten)]
+    generate: GenerateArgs,
+}
+
+#[derive(clap::Subcommand)]
+enum Command {
+    /// Infers the types from example JSON documents, when there's no schema for them
+    Infer(InferArgs),
+
+    /// Generates the types of the component schemas and the operations of an OpenAPI 3.0 or 3.1
+    /// document
+    Openapi(OpenApiArgs),
+
+    /// Generates the `spec` and `status` types of a Kubernetes `CustomResourceDefinition`
+    Crd(CrdArgs),
+
+    /// Exports a schema file as an Avro record schema
+    Avro(AvroArgs),
+}
+
+#[derive(clap::Args)]
+struct GenerateArgs {
+    /// The schema files, or directories to search for `.json`, `.yaml`, `.yml`, `.toml` and `.avsc`
+    /// schemas, a `.jtd.json` file is read as a JSON Type Definition
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// The directory the modules are written to
+    #[arg(short, long, default_value = ".")]
+    out_dir: PathBuf,
+
+    /// Doesn't write anything, fails if a module on disk isn't up to date, or if it's not
+    /// generated from any schema
+    #[arg(long)]
+    check: bool,
+
+    /// Also writes the TypeScript declarations of every schema to this `.d.ts` file
+    #[arg(long, value_name = "FILE")]
+    typescript: Option<PathBuf>,
+
+    #[command(flatten)]
+    options: OptionsArgs,
+}
+
+#[derive(clap::Args)]
+struct InferArgs {
+    /// The example documents, a document holding an array counts as an example for each item
+    #[arg(required = true)]
+    examples: Vec<PathBuf>,
+
+    /// The name of the root struct
+    #[arg(long)]
+    name: String,
+
+    /// The file the module is written to, it's printed if there's none
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Outputs the inferred JSON Schema instead of the Rust types
+    #[arg(long)]
+    schema: bool,
+
+    /// Doesn't write anything, fails if the output file isn't up to date
+    #[arg(long, requires = "out")]
+    check: bool,
+
+    #[command(flatten)]
+    options: OptionsArgs,
+}
+
+#[derive(clap::Args)]
+struct OpenApiArgs {
+    /// The OpenAPI document, JSON, YAML or TOML by it's extension
+    document: PathBuf,
+
+    /// A component to generate, every one of them is generated if there's neither a component
+    /// nor an operation
+    #[arg(long = "component", value_name = "NAME")]
+    components: Vec<String>,
+
+    /// An operation to generate the parameters, request and responses of, by it's `operationId`
+    #[arg(long = "operation", value_name = "ID")]
+    operations: Vec<String>,
+
+    /// The file the module is written to, it's printed if there's none
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Doesn't write anything, fails if the output file isn't up to date
+    #[arg(long, requires = "out")]
+    check: bool,
+
+    #[command(flatten)]
+    options: OptionsArgs,
+}
+
+#[derive(clap::Args)]
+struct CrdArgs {
+    /// The `CustomResourceDefinition` manifest, YAML or JSON by it's extension
+    manifest: PathBuf,
+
+    /// The version to generate, the storage version if there's none
+    #[arg(long)]
+    version: Option<String>,
+
+    /// The file the module is written to, it's printed if there's none
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Doesn't write anything, fails if the output file isn't up to date
+    #[arg(long, requires = "out")]
+    check: bool,
+
+    #[command(flatten)]
+    options: OptionsArgs,
+}
+
+#[derive(clap::Args)]
+struct AvroArgs {
+    /// The schema file, it's root has to be an object
+    schema: PathBuf,
+
+    /// The namespace of the record, like `com.example`
+    #[arg(long)]
+    namespace: Option<String>,
+
+    /// The file the schema is written to, it's printed if there's none
+    #[arg(short, long)]
+    out: Option<PathBuf>,
+
+    /// Doesn't write anything, fails if the output file isn't up to date
+    #[arg(long, requires = "out")]
+    check: bool,
+}
+
+/// the flags shared by every command, they end up in the generation [`Options`]
+#[derive(clap::Args)]
+struct OptionsArgs {
+    /// An extra derive on every generated type, like `PartialEq`
+    #[arg(long = "derive", value_name = "PATH")]
+    derives: Vec<String>,
+
+    /// An extra attribute on every generated type, like `#[serde(deny_unknown_fields)]`
+    #[arg(long = "attr", value_name = "ATTR")]
+    attrs: Vec<String>,
+
+    /// Normalizes the keys with a serde rename rule, like `camelCase`
+    #[arg(long, value_name = "RULE")]
+    rename_all: Option<String>,
+
+    /// The visibility of every generated item
+    #[arg(long, default_value = "pub")]
+    vis: String,
+
+    /// Replaces the type of a schema type or format, like `date-time=chrono::DateTime<chrono::Utc>`
+    #[arg(long = "type-map", value_name = "KEY=TYPE")]
+    type_mappings: Vec<String>,
+
+    /// Implements `schemars::JsonSchema`, it needs the `schemars` feature of `schema2struct`
+    #[arg(long)]
+    schemars: bool,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Some(Command::Infer(args)) => infer(args),
+        Some(Command::Openapi(args)) => openapi(args),
+        Some(Command::Crd(args)) => crd(args),
+        Some(Command::Avro(args)) => export_avro(args),
+        None => run(&cli.generate),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// returns whether everything is up to date, which is always the case when writing
+fn run(args: &GenerateArgs) -> Result<bool, String> {
+    let options = options(&args.options)?;
+    let schemas = read_schemas(&args.inputs)?;
+
+    let mut modules = generate_modules(&schemas, &options)?
+        .into_iter()
+        .map(|(file_name, code)| (args.out_dir.join(file_name), code))
+        .collect::<Vec<_>>();
+
+    // the declarations are checked and written along with the modules
+    if let Some(typescript) = &args.typescript {
+        let schemas = schemas
+            .into_iter()
+            .map(|(_, schema)| schema)
+            .collect::<Vec<_>>();
+
+        modules.push((
+            typescript.clone(),
+            generate_declarations(&schemas, &options),
+        ));
+    }
+
+    if args.check {
+        let up_to_date = check(&modules)?;
+        let expected = check_unexpected(&args.out_dir, &modules)?;
+
+        return Ok(up_to_date && expected);
+    }
+
+    // a module with the same name as a hand written one, like `main.rs` or `mod.rs`, can't replace it
+    for (path, _) in &modules {
+        if path.exists() && !is_generated(path) {
+            return Err(format!(
+                "{} isn't generated by schema2struct, it won't be overwritten",
+                path.display()
+            ));
+        }
+    }
+
+    std::fs::create_dir_all(&args.out_dir)
+        .map_err(|e| format!("{}: {}", args.out_dir.display(), e))?;
+
+    write(&modules)?;
+
+    Ok(true)
+}
+
+// infers the schema of the examples, then generates it like any other schema
+fn infer(args: &InferArgs) -> Result<bool, String> {
+    let options = options(&args.options)?;
+
+    let examples = args
+        .examples
+        .iter()
+        .map(|path| std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e)))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut samples = Vec::new();
+
+    for (path, example) in args.examples.iter().zip(&examples) {
+        samples.extend(
+            parse_examples([example.as_str()]).map_err(|e| format!("{}: {}", path.display(), e))?,
+        );
+    }
+
+    let mut schema = infer_schema(&samples)?;
+
+    // the title names the root struct when the inferred schema is read back
+    schema.title = Some(args.name.clone());
+    schema.struct_name = Some(type_ident(None, &args.name).to_string());
+
+    let output = if args.schema {
+        serde_json::to_string_pretty(&schema.to_json_schema()).map_err(|e| e.to_string())? + "\n"
+    } else {
+        format_code(generate(&schema, &options).map_err(|e| e.to_string())?)
+            .map_err(|e| e.to_string())?
+    };
+
+    output_file(args.out.as_deref(), args.check, output)
+}
+
+// generates the selected components and operations of the document into a single module, they
+// share their types
+fn openapi(args: &OpenApiArgs) -> Result<bool, String> {
+    let options = options(&args.options)?;
+
+    let document = OpenApiDocument::read(&args.document).map_err(|e| e.to_string())?;
+
+    // the operations only bring the components they use
+    let components = match (args.components.is_empty(), args.operations.is_empty()) {
+        (true, true) => document.component_names().map(ToOwned::to_owned).collect(),
+        _ => args.components.clone(),
+    };
+
+    let schemas = components
+        .iter()
+        .map(|name| document.component(name).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let operations = args
+        .operations
+        .iter()
+        .map(|id| document.operation(id).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let tokens = generate_operations(schemas, &operations, &options)
+        .map_err(|e| format!("{}: {}", args.document.display(), e))?;
+
+    let output = format_code(tokens).map_err(|e| e.to_string())?;
+
+    output_file(args.out.as_deref(), args.check, output)
+}
+
+// generates the `spec` and `status` of a version of the custom resource into a single module
+fn crd(args: &CrdArgs) -> Result<bool, String> {
+    let options = options(&args.options)?;
+
+    let crd = CustomResourceDefinition::read(&args.manifest).map_err(|e| e.to_string())?;
+    let schemas = crd
+        .schemas(args.version.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    let tokens = generate_all(&schemas, &options)
+        .map_err(|e| format!("{}: {}", args.manifest.display(), e))?;
+
+    let output = format_code(tokens).map_err(|e| e.to_string())?;
+
+    output_file(args.out.as_deref(), args.check, output)
+}
+
+// exports the schema as an Avro record, the same types the Rust code is generated from
+fn export_avro(args: &AvroArgs) -> Result<bool, String> {
+    let schema = read_schema_file(&args.schema).map_err(|e| e.to_string())?;
+
+    let record = avro::export(&schema, args.namespace.as_deref())
+        .map_err(|e| format!("{}: {}", args.schema.display(), e))?;
+
+    let output = serde_json::to_string_pretty(&record).map_err(|e| e.to_string())? + "\n";
+
+    output_file(args.out.as_deref(), args.check, output)
+}
+
+// writes or checks a single output file, or prints it if there's none
+fn output_file(out: Option<&Path>, check_only: bool, output: String) -> Result<bool, String> {
+    match out {
+        Some(out) if check_only => check(&[(out.to_owned(), output)]),
+        Some(out) => {
+            write(&[(out.to_owned(), output)])?;
+            Ok(true)
+        }
+        None => {
+            print!("{}", output);
+            Ok(true)
+        }
+    }
+}
+
+fn write(files: &[(PathBuf, String)]) -> Result<(), String> {
+    for (path, code) in files {
+        std::fs::write(path, code).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        println!("wrote {}", path.display());
+    }
+
+    Ok(())
+}
+
+// builds the generation options from the flags, they're parsed the same way the macro does
+fn options(args: &OptionsArgs) -> Result<Options, String> {
+    let mut options = Options {
+        schemars: args.schemars,
+        ..Options::default()
+    };
+
+    for derive in &args.derives {
+        options.derives.push(
+            syn::parse_str(derive).map_err(|e| format!("invalid derive `{}`: {}", derive, e))?,
+        );
+    }
+
+    for attr in &args.attrs {
+        options.attrs.extend(
+            syn::Attribute::parse_outer
+                .parse_str(attr)
+                .map_err(|e| format!("invalid attribute `{}`: {}", attr, e))?,
+        );
+    }
+
+    if let Some(rule) = &args.rename_all {
+        options.rename_all = Some(
+            RenameAll::try_from(syn::LitStr::new(rule, proc_macro2::Span::call_site()))
+                .map_err(|e| e.to_string())?,
+        );
+    }
+
+    options.vis = syn::parse_str(&args.vis)
+        .map_err(|e| format!("invalid visibility `{}`: {}", args.vis, e))?;
+
+    for mapping in &args.type_mappings {
+        let Some((key, ty)) = mapping.split_once('=') else {
+            return Err(format!(
+                "invalid type mapping `{}`, expected `KEY=TYPE`",
+                mapping
+            ));
+        };
+
+        let ty = syn::parse_str(ty)
+            .map_err(|e| format!("invalid type in mapping `{}`: {}", mapping, e))?;
+
+        options.type_mappings.insert(key.trim().to_owned(), ty);
+    }
+
+    Ok(options)
+}
+
+// reads every schema file, the directories are searched for them
+fn read_schemas(inputs: &[PathBuf]) -> Result<Vec<(PathBuf, JsonSchema)>, String> {
+    let mut schema_files = Vec::new();
+
+    for input in inputs {
+        if input.is_dir() {
+            schema_files.extend(schema_files_in(input).map_err(|e| e.to_string())?);
+        } else {
+            schema_files.push(input.clone());
+        }
+    }
+
+    schema_files
+        .into_iter()
+        .map(|path| {
+            let schema = read_schema_file(&path).map_err(|e| e.to_string())?;
+
+            Ok((path, schema))
+        })
+        .collect()
+}
+
+// generates a module for every schema, and the `mod.rs` declaring them
+//
+// the modules are keyed by their file name, in the order the schemas were found
+fn generate_modules(
+    schemas: &[(PathBuf, JsonSchema)],
+    options: &Options,
+) -> Result<Vec<(String, String)>, String> {
+    let mut modules = Vec::new();
+    let mut module_idents = Vec::new();
+    let mut module_names = HashMap::<String, &Path>::new();
+
+    for (path, schema) in schemas {
+        let struct_name = schema.struct_name.clone().unwrap_or_default();
+
+        // a keyword like `type` is a raw `mod r#type;`, it's file is still `type.rs`
+        let module_ident = field_ident(&struct_name).ident;
+        let module_name = module_ident.to_string().trim_start_matches("r#").to_owned();
+
+        if let Some(other) = module_names.insert(module_name.clone(), path) {
+            return Err(format!(
+                "{} and {} both generate the `{}` module, give one of them a different `title`",
+                other.display(),
+                path.display(),
+                module_name
+            ));
+        }
+
+        let tokens = generate(schema, options).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        let code = format_code(tokens).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+        modules.push((format!("{}.rs", module_name), code));
+        module_idents.push(module_ident);
+    }
+
+    let vis = &options.vis;
+
+    let mod_file = format_code(quote! {
+        #(#vis mod #module_idents;)*
+    })
+    .map_err(|e| e.to_string())?;
+
+    modules.push((MOD_FILE.to_owned(), mod_file));
+
+    Ok(modules)
+}
+
+// lists every generated module in the output directory that isn't generated anymore, like the
+// module of a schema that was removed or renamed, the hand written ones are left alone
+fn check_unexpected(out_dir: &Path, files: &[(PathBuf, String)]) -> Result<bool, String> {
+    let entries = match std::fs::read_dir(out_dir) {
+        Ok(entries) => entries,
+        // it's missing along with every module in it, that's already reported
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => return Err(format!("{}: {}", out_dir.display(), e)),
+    };
+
+    let mut unexpected = Vec::new();
+
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("{}: {}", out_dir.display(), e))?
+            .path();
+
+        let is_module = path.extension().is_some_and(|extension| extension == "rs");
+
+        if is_module && !files.iter().any(|(file, _)| *file == path) && is_generated(&path) {
+            unexpected.push(path);
+        }
+    }
+
+    // the same order every time
+    unexpected.sort();
+
+    for path in &unexpected {
+        eprintln!(
+            "{} isn't generated from any schema, it can be removed",
+            path.display()
+        );
+    }
+
+    Ok(unexpected.is_empty())
+}
+
+// whether the file starts with the header of the generated files
+fn is_generated(path: &Path) -> bool {
+    std::fs::read_to_string(path).is_ok_and(|content| content.starts_with(GENERATED_HEADER))
+}
+
+// compares the generated files with the ones on disk, listing every stale one
+fn check(files: &[(PathBuf, String)]) -> Result<bool, String> {
+    let mut up_to_date = true;
+
+    for (path, code) in files {
+        match std::fs::read_to_string(path) {
+            Ok(on_disk) if on_disk == *code => {}
+            Ok(_) => {
+                eprintln!("{} is out of date", path.display());
+                up_to_date = false;
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                eprintln!("{} is missing", path.display());
+                up_to_date = false;
+            }
+            Err(e) => return Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    if !up_to_date {
+        eprintln!("run `schema2struct` without `--check` to regenerate them");
+    }
+
+    Ok(up_to_date)
+}
@@ -0,0 +1,305 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+const USER: &str = "title: User\ntype: object\nrequired: [name]\nproperties:\n  name:\n    type: string\n  age:\n    type: integer\n";
+
+const ORDER: &str =
+    r#"{ "title": "Order", "type": "object", "properties": { "id": { "type": "string" } } }"#;
+
+/// an empty directory of it's own for the test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir =
+        std::env::temp_dir().join(format!("schema2struct-cli-{}-{}", std::process::id(), name));
+
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+fn write(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn example(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../examples")
+        .join(path)
+}
+
+fn schema2struct(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_schema2struct"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
+
+// a directory with two schemas, generated into `out`
+fn generated(name: &str) -> PathBuf {
+    let dir = temp_dir(name);
+
+    write(&dir.join("schemas/user.yaml"), USER);
+    write(&dir.join("schemas/order.json"), ORDER);
+
+    let output = schema2struct(
+        &dir,
+        &["schemas", "--out-dir", "out", "--derive", "PartialEq"],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    dir
+}
+
+#[test]
+fn a_module_is_generated_for_every_schema() {
+    let dir = generated("generate");
+
+    let user = std::fs::read_to_string(dir.join("out/user.rs")).unwrap();
+    let mod_file = std::fs::read_to_string(dir.join("out/mod.rs")).unwrap();
+
+    assert!(user.starts_with("// @generated by schema2struct"));
+    assert!(user.contains("pub struct User"));
+    assert!(user.contains("PartialEq"));
+    assert!(user.contains("pub age: Option<i64>"));
+    assert!(dir.join("out/order.rs").exists());
+    assert!(mod_file.contains("pub mod order;\npub mod user;"));
+
+    let output = schema2struct(
+        &dir,
+        &[
+            "schemas",
+            "--out-dir",
+            "out",
+            "--derive",
+            "PartialEq",
+            "--check",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn check_reports_the_stale_and_the_left_over_modules() {
+    let dir = generated("check");
+
+    // a schema changed, another one was removed
+    write(
+        &dir.join("schemas/user.yaml"),
+        &USER.replace("User", "Account"),
+    );
+    std::fs::remove_file(dir.join("schemas/order.json")).unwrap();
+
+    // a hand written file in the same directory isn't reported
+    write(&dir.join("out/helpers.rs"), "pub fn helper() {}\n");
+
+    let output = schema2struct(
+        &dir,
+        &[
+            "schemas",
+            "--out-dir",
+            "out",
+            "--derive",
+            "PartialEq",
+            "--check",
+        ],
+    );
+    let stderr = stderr(&output);
+
+    assert!(!output.status.success());
+    assert!(stderr.contains("account.rs is missing"), "{}", stderr);
+    assert!(stderr.contains("mod.rs is out of date"), "{}", stderr);
+    assert!(
+        stderr.contains("order.rs isn't generated from any schema"),
+        "{}",
+        stderr
+    );
+    assert!(
+        stderr.contains("user.rs isn't generated from any schema"),
+        "{}",
+        stderr
+    );
+    assert!(!stderr.contains("helpers.rs"), "{}", stderr);
+
+    // nothing was written
+    assert!(!dir.join("out/account.rs").exists());
+}
+
+#[test]
+fn a_hand_written_module_is_not_overwritten() {
+    let dir = temp_dir("hand-written");
+
+    write(
+        &dir.join("schemas/main.yaml"),
+        &USER.replace("User", "Main"),
+    );
+    write(&dir.join("src/main.rs"), "fn main() {}\n");
+
+    let output = schema2struct(&dir, &["schemas", "--out-dir", "src"]);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output)
+            .contains("main.rs isn't generated by schema2struct, it won't be overwritten"),
+        "{}",
+        stderr(&output)
+    );
+    assert_eq!(
+        std::fs::read_to_string(dir.join("src/main.rs")).unwrap(),
+        "fn main() {}\n"
+    );
+}
+
+#[test]
+fn infer_generates_the_types_of_examples() {
+    let dir = temp_dir("infer");
+
+    write(&dir.join("a.json"), r#"{ "id": 1, "tags": ["a"] }"#);
+    write(&dir.join("b.json"), r#"[{ "id": 2, "note": "x" }]"#);
+
+    let output = schema2struct(&dir, &["infer", "a.json", "b.json", "--name", "Item"]);
+    let code = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(code.contains("pub struct Item"), "{}", code);
+    assert!(code.contains("pub id: i64"), "{}", code);
+    assert!(code.contains("pub note: Option<String>"), "{}", code);
+
+    let output = schema2struct(&dir, &["infer", "a.json", "--name", "Item", "--schema"]);
+    let schema = serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap();
+
+    assert_eq!(schema["title"], "Item");
+    assert_eq!(schema["properties"]["tags"]["type"], "array");
+}
+
+#[test]
+fn openapi_generates_the_components_and_operations() {
+    let dir = temp_dir("openapi");
+    let document = example("openapi/petstore.yaml");
+    let document = document.to_str().unwrap();
+
+    let output = schema2struct(&dir, &["openapi", document, "--component", "Pet"]);
+    let code = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(code.contains("pub enum Pet"), "{}", code);
+    assert!(!code.contains("pub struct Owner"), "{}", code);
+
+    let output = schema2struct(
+        &dir,
+        &[
+            "openapi",
+            document,
+            "--operation",
+            "getOwner",
+            "--out",
+            "api.rs",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let code = std::fs::read_to_string(dir.join("api.rs")).unwrap();
+
+    assert!(code.contains("pub enum GetOwnerResponse"), "{}", code);
+    assert!(code.contains("pub struct Owner"), "{}", code);
+
+    let output = schema2struct(
+        &dir,
+        &[
+            "openapi",
+            document,
+            "--operation",
+            "getOwner",
+            "--out",
+            "api.rs",
+            "--check",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let output = schema2struct(&dir, &["openapi", document, "--operation", "deleteOwner"]);
+
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("deleteOwner"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn crd_generates_the_spec_and_status() {
+    let dir = temp_dir("crd");
+    let manifest = example("kubernetes/crontab.yaml");
+
+    let output = schema2struct(
+        &dir,
+        &["crd", manifest.to_str().unwrap(), "--version", "v1"],
+    );
+    let code = stdout(&output);
+
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(code.contains("pub struct CronTabSpec"), "{}", code);
+
+    let output = schema2struct(
+        &dir,
+        &["crd", manifest.to_str().unwrap(), "--version", "v2"],
+    );
+
+    assert!(!output.status.success());
+    assert!(stderr(&output).contains("v2"), "{}", stderr(&output));
+}
+
+#[test]
+fn avro_exports_a_record() {
+    let dir = temp_dir("avro");
+
+    write(&dir.join("user.yaml"), USER);
+
+    let output = schema2struct(
+        &dir,
+        &[
+            "avro",
+            "user.yaml",
+            "--namespace",
+            "com.example",
+            "--out",
+            "user.avsc",
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+
+    let record = serde_json::from_str::<serde_json::Value>(
+        &std::fs::read_to_string(dir.join("user.avsc")).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(record["type"], "record");
+    assert_eq!(record["name"], "User");
+    assert_eq!(record["namespace"], "com.example");
+
+    write(&dir.join("user.avsc"), "{}\n");
+
+    let output = schema2struct(
+        &dir,
+        &["avro", "user.yaml", "--out", "user.avsc", "--check"],
+    );
+
+    assert!(!output.status.success());
+    assert!(
+        stderr(&output).contains("user.avsc is out of date"),
+        "{}",
+        stderr(&output)
+    );
+}
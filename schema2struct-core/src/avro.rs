@@ -0,0 +1,928 @@
+/// Avro module
+///
+/// Lowers an Avro schema (`.avsc`) into the same [`JsonSchema`] model, so it goes through the same
+/// generator, and exports a schema back into an Avro one, so the Rust producers and the JVM
+/// consumers share a single source
+///
+/// # Lowering
+/// - A `record` becomes a struct named after it, every field of it is required
+/// - An `enum` becomes a string that only takes it's symbols
+/// - An `array` becomes a `Vec`, and a `map` a `BTreeMap` with `String` keys
+/// - A union with `null` becomes an `Option`, any other union becomes an untagged enum
+/// - `bytes` and `fixed` are a string, like in the Avro JSON encoding
+/// - A logical type keeps it's underlying type, `timestamp-millis` is the `i64` of milliseconds
+/// - A named type can be referenced by it's name once it's defined
+///
+/// # Exporting
+/// An object becomes a `record`, a property that's optional or nullable is a union with `null`
+/// that defaults to it, and a string with an `enum` becomes an Avro `enum`. A `format` stays a
+/// `string`, since that's what the Rust type is
+///
+/// A schema lowered from an Avro one keeps what the model can't hold, like it's `int` width, it's
+/// logical types and it's namespaces, so it's exported back the same, and a named type is defined
+/// once and referenced by it's name after
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    checkers::closest_match,
+    jtd::join,
+    models::{AvroType, JsonSchema, JsonSchemaTypes, JsonSchemaValues},
+    naming::type_ident,
+};
+
+/// the types that don't need to be defined
+const PRIMITIVES: [&str; 8] = [
+    "null", "boolean", "int", "long", "float", "double", "bytes", "string",
+];
+
+/// Why a schema can't be lowered or exported, with where it is in the schema, like `fields.id`
+pub struct AvroError {
+    pub location: String,
+    pub message: String,
+}
+
+impl AvroError {
+    fn new(location: &str, message: impl Into<String>) -> Self {
+        Self {
+            location: location.to_owned(),
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for AvroError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.location.is_empty() {
+            true => f.write_str(&self.message),
+            false => write!(f, "{}: {}", self.location, self.message),
+        }
+    }
+}
+
+/// Lowers an Avro schema, it's root has to be a `record`
+///
+/// # Errors
+/// - The root isn't a `record`
+/// - A type isn't a primitive, and it's not defined before it's used
+/// - A named type is defined twice, or it references itself
+/// - A `record`, `enum`, `array`, `map` or `fixed` is missing one of it's attributes
+pub fn lower(document: &Value) -> Result<JsonSchema, AvroError> {
+    if !matches!(
+        document.get("type").and_then(Value::as_str),
+        Some("record" | "error")
+    ) {
+        return Err(AvroError::new("", "the root schema must be a `record`"));
+    }
+
+    let mut lowering = Lowering {
+        names: HashMap::new(),
+    };
+
+    lowering.lower(document, "", None)
+}
+
+struct Lowering {
+    /// every named type by it's full name, it's `None` while it's being lowered
+    names: HashMap<String, Option<JsonSchema>>,
+}
+
+impl Lowering {
+    fn lower(
+        &mut self,
+        schema: &Value,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        let object = match schema {
+            Value::String(name) => return self.named(name, location, namespace),
+            Value::Array(branches) => return self.union(branches, location, namespace),
+            Value::Object(object) => object,
+            _ => {
+                return Err(AvroError::new(
+                    location,
+                    "a schema is a type name, a union or an object",
+                ))
+            }
+        };
+
+        let ty = match object.get("type") {
+            Some(Value::String(ty)) => ty,
+            // `{ "type": ["null", "string"] }` is the same as the union itself
+            Some(ty) => return self.lower(ty, &join(location, "type"), namespace),
+            None => return Err(AvroError::new(location, "a schema needs a `type`")),
+        };
+
+        match ty.as_str() {
+            "record" | "error" => self.record(object, location, namespace),
+            "enum" => self.enumeration(object, location, namespace),
+            "array" => Ok(JsonSchema {
+                ty: JsonSchemaTypes::Array,
+                items: Some(Box::new(self.lower(
+                    attribute(object, "items", location)?,
+                    &join(location, "items"),
+                    namespace,
+                )?)),
+                ..Default::default()
+            }),
+            "map" => Ok(JsonSchema {
+                ty: JsonSchemaTypes::Object,
+                additional_properties: Some(Box::new(self.lower(
+                    attribute(object, "values", location)?,
+                    &join(location, "values"),
+                    namespace,
+                )?)),
+                ..Default::default()
+            }),
+            // it's a string in the JSON encoding
+            "fixed" => {
+                let (full_name, _, _) = self.declare(object, location, namespace)?;
+
+                let schema = JsonSchema {
+                    ty: JsonSchemaTypes::String,
+                    description: doc(object),
+                    avro: Some(named_type(&full_name, object, &[])),
+                    ..Default::default()
+                };
+
+                self.names.insert(full_name, Some(schema.clone()));
+
+                Ok(schema)
+            }
+            // a primitive with a `logicalType`, it keeps it's underlying type
+            ty => {
+                let mut lowered = self.named(ty, &join(location, "type"), namespace)?;
+
+                if PRIMITIVES.contains(&ty) {
+                    lowered.avro = Some(AvroType::Primitive(schema.clone()));
+                }
+
+                Ok(lowered)
+            }
+        }
+    }
+
+    // a primitive, or a named type that's already defined
+    fn named(
+        &self,
+        name: &str,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        let ty = match name {
+            "null" => return Ok(JsonSchema::default()),
+            "boolean" => JsonSchemaTypes::Boolean,
+            "int" | "long" => JsonSchemaTypes::Integer,
+            "float" | "double" => JsonSchemaTypes::Number,
+            "bytes" | "string" => JsonSchemaTypes::String,
+            _ => return self.reference(name, location, namespace),
+        };
+
+        Ok(JsonSchema {
+            ty,
+            // an `int` is still an `int` when it's exported
+            avro: Some(AvroType::Primitive(name.into())),
+            ..Default::default()
+        })
+    }
+
+    fn reference(
+        &self,
+        name: &str,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        // a short name is in the enclosing namespace, or the only type with that name
+        let qualified = namespace.map(|namespace| format!("{}.{}", namespace, name));
+        let suffix = format!(".{}", name);
+
+        let found = self
+            .names
+            .get(name)
+            .or_else(|| qualified.and_then(|qualified| self.names.get(&qualified)))
+            .or_else(|| {
+                let mut matching = self
+                    .names
+                    .iter()
+                    .filter(|(full_name, _)| full_name.ends_with(&suffix));
+
+                match (matching.next(), matching.next()) {
+                    (Some((_, schema)), None) => Some(schema),
+                    _ => None,
+                }
+            });
+
+        match found {
+            Some(Some(schema)) => Ok(schema.clone()),
+            Some(None) => Err(AvroError::new(
+                location,
+                format!(
+                    "`{}` references itself, recursive types aren't supported",
+                    name
+                ),
+            )),
+            None => {
+                let candidates = PRIMITIVES
+                    .into_iter()
+                    .chain(self.names.keys().map(|full_name| short_name(full_name)))
+                    .collect::<Vec<_>>();
+
+                let message = match closest_match(name, &candidates) {
+                    Some(suggestion) => {
+                        format!("unknown type `{}`, did you mean `{}`?", name, suggestion)
+                    }
+                    None => format!(
+                        "unknown type `{}`, a named type has to be defined before it's used",
+                        name
+                    ),
+                };
+
+                Err(AvroError::new(location, message))
+            }
+        }
+    }
+
+    fn union(
+        &mut self,
+        branches: &[Value],
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        let nullable = branches.iter().any(|branch| branch == "null");
+
+        let mut lowered = Vec::new();
+
+        for (index, branch) in branches.iter().enumerate() {
+            if branch != "null" {
+                lowered.push(self.lower(branch, &format!("{}[{}]", location, index), namespace)?);
+            }
+        }
+
+        let mut schema = match <[_; 1]>::try_from(lowered) {
+            Ok([schema]) => schema,
+            Err(lowered) if lowered.is_empty() => return Ok(JsonSchema::default()),
+            // every branch is a variant, named after it's record or it's type
+            Err(lowered) => JsonSchema {
+                one_of: Some(lowered),
+                ..Default::default()
+            },
+        };
+
+        if nullable {
+            schema.nullable = Some(true);
+        }
+
+        Ok(schema)
+    }
+
+    fn record(
+        &mut self,
+        object: &Map<String, Value>,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        let (full_name, name, namespace) = self.declare(object, location, namespace)?;
+
+        let Some(fields) = object.get("fields").and_then(Value::as_array) else {
+            return Err(AvroError::new(
+                &join(location, "fields"),
+                "a `record` needs a `fields` array",
+            ));
+        };
+
+        let mut properties = IndexMap::new();
+
+        for (index, field) in fields.iter().enumerate() {
+            let field_location = format!("{}[{}]", join(location, "fields"), index);
+
+            let Some(field_name) = field.get("name").and_then(Value::as_str) else {
+                return Err(AvroError::new(&field_location, "a field needs a `name`"));
+            };
+
+            let field_location = join(&join(location, "fields"), field_name);
+
+            if properties.contains_key(field_name) {
+                return Err(AvroError::new(
+                    &field_location,
+                    format!("`{}` is defined twice", field_name),
+                ));
+            }
+
+            let Some(ty) = field.get("type") else {
+                return Err(AvroError::new(&field_location, "a field needs a `type`"));
+            };
+
+            let mut property =
+                self.lower(ty, &join(&field_location, "type"), namespace.as_deref())?;
+
+            // a named type is shared by every field using it, so it keeps it's own doc
+            if let Some(description) = field.as_object().and_then(doc) {
+                if !matches!(property.avro, Some(AvroType::Named { .. })) {
+                    property.description = Some(description);
+                }
+            }
+
+            properties.insert(field_name.to_owned(), property);
+        }
+
+        let schema = JsonSchema {
+            ty: JsonSchemaTypes::Object,
+            title: Some(name.clone()),
+            description: doc(object),
+            required: Some(properties.keys().cloned().collect()),
+            properties: Some(properties),
+            struct_name: Some(name),
+            avro: Some(named_type(&full_name, object, &["fields"])),
+            ..Default::default()
+        };
+
+        self.names.insert(full_name, Some(schema.clone()));
+
+        Ok(schema)
+    }
+
+    fn enumeration(
+        &mut self,
+        object: &Map<String, Value>,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<JsonSchema, AvroError> {
+        let (full_name, _, _) = self.declare(object, location, namespace)?;
+
+        let symbols = attribute(object, "symbols", location)?
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|symbol| match symbol {
+                Value::String(symbol) => Ok(JsonSchemaValues::Str(symbol.clone())),
+                _ => Err(AvroError::new(
+                    &join(location, "symbols"),
+                    "every symbol must be a string",
+                )),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let schema = JsonSchema {
+            ty: JsonSchemaTypes::String,
+            description: doc(object),
+            enum_values: Some(symbols),
+            avro: Some(named_type(&full_name, object, &["symbols"])),
+            ..Default::default()
+        };
+
+        self.names.insert(full_name, Some(schema.clone()));
+
+        Ok(schema)
+    }
+
+    // takes the name of a named type before it's lowered
+    //
+    // returns it's full name, it's short name, and the namespace of the types defined in it
+    fn declare(
+        &mut self,
+        object: &Map<String, Value>,
+        location: &str,
+        namespace: Option<&str>,
+    ) -> Result<(String, String, Option<String>), AvroError> {
+        let Some(name) = object.get("name").and_then(Value::as_str) else {
+            return Err(AvroError::new(
+                location,
+                "a `record`, `enum` or `fixed` needs a `name`",
+            ));
+        };
+
+        let (full_name, namespace) = match name.rsplit_once('.') {
+            Some((namespace, _)) => (name.to_owned(), Some(namespace.to_owned())),
+            None => {
+                let namespace = object
+                    .get("namespace")
+                    .and_then(Value::as_str)
+                    .or(namespace)
+                    .filter(|namespace| !namespace.is_empty());
+
+                match namespace {
+                    Some(namespace) => (
+                        format!("{}.{}", namespace, name),
+                        Some(namespace.to_owned()),
+                    ),
+                    None => (name.to_owned(), None),
+                }
+            }
+        };
+
+        if self.names.contains_key(&full_name) {
+            return Err(AvroError::new(
+                &join(location, "name"),
+                format!("`{}` is defined twice", full_name),
+            ));
+        }
+
+        self.names.insert(full_name.clone(), None);
+
+        let name = short_name(&full_name).to_owned();
+
+        Ok((full_name, name, namespace))
+    }
+}
+
+// `com.example.User` is `User`
+fn short_name(full_name: &str) -> &str {
+    full_name.rsplit('.').next().unwrap_or(full_name)
+}
+
+fn attribute<'a>(
+    object: &'a Map<String, Value>,
+    key: &str,
+    location: &str,
+) -> Result<&'a Value, AvroError> {
+    object.get(key).ok_or_else(|| {
+        let ty = object
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+
+        let article = match ty.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            true => "an",
+            false => "a",
+        };
+
+        AvroError::new(location, format!("{} `{}` needs `{}`", article, ty, key))
+    })
+}
+
+// a named type, with the attributes the model doesn't hold, `lowered` are the ones it does
+fn named_type(full_name: &str, object: &Map<String, Value>, lowered: &[&str]) -> AvroType {
+    let attributes = object
+        .iter()
+        .filter(|(key, _)| {
+            !["type", "name", "namespace", "doc"].contains(&key.as_str())
+                && !lowered.contains(&key.as_str())
+        })
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect();
+
+    AvroType::Named {
+        full_name: full_name.to_owned(),
+        attributes,
+    }
+}
+
+fn doc(object: &Map<String, Value>) -> Option<String> {
+    object
+        .get("doc")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+/// Exports a schema as an Avro `record`, named after it's struct name or it's `title`
+///
+/// a schema that was lowered from an Avro one is exported back the same, with it's names,
+/// namespaces, widths and logical types
+///
+/// # Arguments
+/// * `schema` - The root schema, it has to be an object with properties
+/// * `namespace` - The namespace of the root record, the nested types are in it too
+///
+/// # Errors
+/// - The root has no name, or it's not an object with properties
+/// - A schema accepts any value, or it's an object without properties, there's no Avro type for them
+/// - A property or an `enum` value isn't a valid Avro name
+/// - The same name is used for different types
+pub fn export(schema: &JsonSchema, namespace: Option<&str>) -> Result<Value, AvroError> {
+    let Some(name) = schema.struct_name.as_ref().or(schema.title.as_ref()) else {
+        return Err(AvroError::new(
+            "",
+            "the root schema needs a `title` to name the record",
+        ));
+    };
+
+    if !matches!(schema.ty, JsonSchemaTypes::Object) || schema.is_map() {
+        return Err(AvroError::new("", "the root schema must be an object"));
+    }
+
+    let name = type_ident(None, name).to_string();
+
+    // the namespace that's asked for replaces the one it was read with
+    let mut schema = schema.clone();
+
+    if let Some(namespace) = namespace {
+        let (name, attributes) = match schema.avro.take() {
+            Some(AvroType::Named {
+                full_name,
+                attributes,
+            }) => (short_name(&full_name).to_owned(), attributes),
+            _ => (name.clone(), Map::new()),
+        };
+
+        schema.avro = Some(AvroType::Named {
+            full_name: format!("{}.{}", namespace, name),
+            attributes,
+        });
+    }
+
+    let mut exporting = Exporting {
+        defined: HashMap::new(),
+        namespace: None,
+    };
+
+    exporting.export(&schema, &name, "")
+}
+
+struct Exporting {
+    /// every named type by it's full name, with the schema it's defined from, the next uses of it
+    /// are by name
+    defined: HashMap<String, Value>,
+
+    /// the namespace of the enclosing named type, the types without their own are in it
+    namespace: Option<String>,
+}
+
+impl Exporting {
+    // `name` is the name of the type the generator gives it, the named types are named after it
+    fn export(
+        &mut self,
+        schema: &JsonSchema,
+        name: &str,
+        location: &str,
+    ) -> Result<Value, AvroError> {
+        let exported = match (&schema.avro, &schema.ty, &schema.one_of) {
+            // it's written back the way it was read, like an `int` or a logical type
+            (Some(AvroType::Primitive(ty)), _, None) => ty.clone(),
+            (_, _, Some(variants)) => {
+                let mut branches = Vec::new();
+
+                for (index, variant) in variants.iter().enumerate() {
+                    let variant_name = match variant.struct_name.as_ref().or(variant.title.as_ref())
+                    {
+                        Some(variant_name) => type_ident(None, variant_name).to_string(),
+                        None => format!("{}{}", name, type_ident(None, &variant.ty.to_string())),
+                    };
+
+                    let branch = self.export(
+                        variant,
+                        &variant_name,
+                        &format!("{}[{}]", join(location, "oneOf"), index),
+                    )?;
+
+                    // a union can't hold another union
+                    match branch {
+                        Value::Array(nested) => branches.extend(nested),
+                        branch => branches.push(branch),
+                    }
+                }
+
+                Value::Array(branches)
+            }
+            (_, JsonSchemaTypes::Object, None) if schema.is_map() => json!({
+                "type": "map",
+                "values": self.export(
+                    schema.additional_properties.as_deref().expect("a map has it's values"),
+                    name,
+                    &join(location, "additionalProperties"),
+                )?,
+            }),
+            (_, JsonSchemaTypes::Object, None) => self.record(schema, name, location)?,
+            (_, JsonSchemaTypes::Array, None) => {
+                let Some(items) = schema.items.as_deref() else {
+                    return Err(AvroError::new(
+                        location,
+                        "an array needs `items` to have an Avro type",
+                    ));
+                };
+
+                json!({
+                    "type": "array",
+                    "items": self.export(items, name, &join(location, "items"))?,
+                })
+            }
+            (_, JsonSchemaTypes::String, None) => match &schema.enum_values {
+                Some(values) => self.enumeration(schema, values, name, location)?,
+                // a named string without symbols is a `fixed`
+                None if schema.avro.is_some() => self.named(schema, "fixed", name, location)?,
+                None => "string".into(),
+            },
+            (_, JsonSchemaTypes::Integer, None) => "long".into(),
+            (_, JsonSchemaTypes::Number, None) => "double".into(),
+            (_, JsonSchemaTypes::Boolean, None) => "boolean".into(),
+            (_, JsonSchemaTypes::None, None) => {
+                return Err(AvroError::new(
+                    location,
+                    "a schema that accepts any value has no Avro type",
+                ))
+            }
+        };
+
+        Ok(match schema.nullable {
+            Some(true) => nullable(exported),
+            _ => exported,
+        })
+    }
+
+    fn record(
+        &mut self,
+        schema: &JsonSchema,
+        name: &str,
+        location: &str,
+    ) -> Result<Value, AvroError> {
+        let name = match schema.struct_name.as_ref().or(schema.title.as_ref()) {
+            Some(name) => type_ident(None, name).to_string(),
+            None => name.to_owned(),
+        };
+
+        let properties = match &schema.properties {
+            Some(properties) if !properties.is_empty() => properties,
+            _ => {
+                return Err(AvroError::new(
+                    location,
+                    "an object without properties has no Avro type",
+                ))
+            }
+        };
+
+        let mut record = match self.named(schema, "record", &name, location)? {
+            Value::Object(record) => record,
+            // it's defined once, like the struct is
+            reference => return Ok(reference),
+        };
+
+        // the fields are in the record's namespace
+        let (full_name, _) = self.full_name(schema, &name);
+        let enclosing = std::mem::replace(&mut self.namespace, namespace_of(&full_name));
+
+        let mut fields = Vec::new();
+
+        for (key, property) in properties {
+            let property_location = join(&join(location, "properties"), key);
+
+            if !is_name(key) {
+                return Err(AvroError::new(
+                    &property_location,
+                    format!("`{}` isn't a valid Avro field name", key),
+                ));
+            }
+
+            let property_name = type_ident(Some(&type_ident(None, &name)), key).to_string();
+            let ty = self.export(property, &property_name, &property_location)?;
+
+            // it's left out of the `required`, so it can be missing
+            let optional = schema
+                .required
+                .as_ref()
+                .is_some_and(|required| !required.contains(key));
+
+            let mut field = Map::new();
+            field.insert("name".into(), key.as_str().into());
+
+            match optional || property.nullable == Some(true) {
+                true => {
+                    field.insert("type".into(), nullable(ty));
+                    field.insert("default".into(), Value::Null);
+                }
+                false => {
+                    field.insert("type".into(), ty);
+                }
+            }
+
+            // a named type keeps it's doc in it's definition
+            if let (Some(description), false) = (
+                &property.description,
+                matches!(property.avro, Some(AvroType::Named { .. })),
+            ) {
+                field.insert("doc".into(), description.as_str().into());
+            }
+
+            fields.push(Value::Object(field));
+        }
+
+        self.namespace = enclosing;
+
+        record.insert("fields".into(), fields.into());
+
+        Ok(record.into())
+    }
+
+    fn enumeration(
+        &mut self,
+        schema: &JsonSchema,
+        values: &[JsonSchemaValues],
+        name: &str,
+        location: &str,
+    ) -> Result<Value, AvroError> {
+        let symbols = values
+            .iter()
+            .map(|value| match value.get_str() {
+                Some(symbol) if is_name(symbol) => Ok(Value::from(symbol.as_str())),
+                _ => Err(AvroError::new(
+                    &join(location, "enum"),
+                    format!("`{}` isn't a valid Avro enum symbol", value),
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut enumeration = match self.named(schema, "enum", name, location)? {
+            Value::Object(enumeration) => enumeration,
+            reference => return Ok(reference),
+        };
+
+        enumeration.insert("symbols".into(), symbols.into());
+
+        Ok(enumeration.into())
+    }
+
+    // the definition of a named type the first time it's used, without it's fields or symbols, and
+    // a reference to it by name after that
+    fn named(
+        &mut self,
+        schema: &JsonSchema,
+        ty: &str,
+        name: &str,
+        location: &str,
+    ) -> Result<Value, AvroError> {
+        let (full_name, attributes) = self.full_name(schema, name);
+
+        // being `null` is up to where it's used, and a field can't change the type's doc
+        let shape = serde_json::to_value(JsonSchema {
+            nullable: None,
+            description: None,
+            ..schema.clone()
+        })
+        .unwrap_or_default();
+
+        match self.defined.get(&full_name) {
+            Some(defined) if *defined == shape => return Ok(self.reference(&full_name)),
+            Some(_) => {
+                return Err(AvroError::new(
+                    location,
+                    format!("`{}` is the name of different types", full_name),
+                ))
+            }
+            None => {
+                self.defined.insert(full_name.clone(), shape);
+            }
+        }
+
+        let mut definition = Map::new();
+        definition.insert("type".into(), ty.into());
+        definition.insert("name".into(), short_name(&full_name).into());
+
+        let namespace = namespace_of(&full_name);
+
+        if namespace != self.namespace {
+            definition.insert("namespace".into(), namespace.unwrap_or_default().into());
+        }
+
+        if let Some(description) = &schema.description {
+            definition.insert("doc".into(), description.as_str().into());
+        }
+
+        definition.extend(attributes);
+
+        Ok(definition.into())
+    }
+
+    // the full name of a named type, the one it was read with, or it's generated name in the
+    // enclosing namespace, and the attributes it was read with
+    fn full_name(&self, schema: &JsonSchema, name: &str) -> (String, Map<String, Value>) {
+        match &schema.avro {
+            Some(AvroType::Named {
+                full_name,
+                attributes,
+            }) => (full_name.clone(), attributes.clone()),
+            _ => match &self.namespace {
+                Some(namespace) => (format!("{}.{}", namespace, name), Map::new()),
+                None => (name.to_owned(), Map::new()),
+            },
+        }
+    }
+
+    // a named type by it's name, the short one if it's in the enclosing namespace
+    fn reference(&self, full_name: &str) -> Value {
+        match namespace_of(full_name) == self.namespace {
+            true => short_name(full_name).into(),
+            false => full_name.into(),
+        }
+    }
+}
+
+// `com.example.User` is in `com.example`
+fn namespace_of(full_name: &str) -> Option<String> {
+    full_name
+        .rsplit_once('.')
+        .map(|(namespace, _)| namespace.to_owned())
+}
+
+// a union with `null` first, so it can be the default
+fn nullable(ty: Value) -> Value {
+    let mut branches = match ty {
+        Value::Array(branches) => branches,
+        ty => vec![ty],
+    };
+
+    branches.retain(|branch| branch != "null");
+    branches.insert(0, "null".into());
+
+    Value::Array(branches)
+}
+
+// starts with a letter or `_`, followed by letters, digits or `_`
+fn is_name(name: &str) -> bool {
+    let mut chars = name.chars();
+
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(schema: Value) -> Value {
+        let lowered = lower(&schema).map_err(|e| e.to_string()).unwrap();
+        export(&lowered, None).map_err(|e| e.to_string()).unwrap()
+    }
+
+    #[test]
+    fn exported_back_the_same() {
+        let schema = json!({
+            "type": "record",
+            "name": "User",
+            "namespace": "com.example",
+            "doc": "a user",
+            "fields": [
+                { "name": "id", "type": "int" },
+                { "name": "created", "type": { "type": "long", "logicalType": "timestamp-millis" } },
+                { "name": "role", "type": { "type": "enum", "name": "Role", "symbols": ["ADMIN", "MEMBER"] } },
+                { "name": "previous", "type": ["null", "Role"], "default": null },
+                { "name": "hash", "type": { "type": "fixed", "name": "Hash", "size": 16 } },
+                { "name": "tags", "type": { "type": "map", "values": "string" } }
+            ]
+        });
+
+        assert_eq!(round_trip(schema.clone()), schema);
+    }
+
+    #[test]
+    fn namespace_is_replaced() {
+        let schema = json!({
+            "type": "record",
+            "name": "User",
+            "namespace": "com.example",
+            "fields": [{ "name": "id", "type": "long" }]
+        });
+
+        let lowered = lower(&schema).map_err(|e| e.to_string()).unwrap();
+        let exported = export(&lowered, Some("org.other"))
+            .map_err(|e| e.to_string())
+            .unwrap();
+
+        assert_eq!(exported["namespace"], "org.other");
+        assert_eq!(exported["name"], "User");
+    }
+
+    #[test]
+    fn name_of_different_types() {
+        let schema = json!({
+            "type": "record",
+            "name": "Order",
+            "fields": [
+                {
+                    "name": "billing",
+                    "type": { "type": "record", "name": "Address", "fields": [{ "name": "zip", "type": "string" }] }
+                },
+                { "name": "shipping", "type": "Address" }
+            ]
+        });
+
+        let mut lowered = lower(&schema).map_err(|e| e.to_string()).unwrap();
+
+        let shipping = &mut lowered.properties.as_mut().unwrap()["shipping"];
+        shipping.properties.as_mut().unwrap()["zip"].ty = JsonSchemaTypes::Integer;
+        shipping.properties.as_mut().unwrap()["zip"].avro = None;
+
+        let error = export(&lowered, None).err().unwrap();
+
+        assert_eq!(error.location, "properties.shipping");
+        assert_eq!(error.message, "`Address` is the name of different types");
+    }
+
+    #[test]
+    fn lowering_errors() {
+        let error = lower(&json!({ "type": "enum", "name": "Role", "symbols": [] }))
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "the root schema must be a `record`");
+
+        let error = lower(&json!({
+            "type": "record",
+            "name": "User",
+            "fields": [{ "name": "role", "type": "Role" }]
+        }))
+        .err()
+        .unwrap();
+        assert_eq!(error.location, "fields.role.type");
+    }
+}
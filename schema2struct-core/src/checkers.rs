@@ -0,0 +1,428 @@
+/// Validation module for JSON Schema parsing
+///
+/// This module provides comprehensive validation checks for parsed JSON schemas,
+/// ensuring type safety, property consistency, and structural integrity.
+///
+/// # Key Validation Checks
+/// - Ensure required properties match schema properties
+/// - Validate type-specific constraints
+/// - Check structural requirements
+///
+use proc_macro2::Span;
+use serde_json::Number;
+
+use crate::models::{JsonSchema, JsonSchemaTypes, JsonSchemaValues};
+
+/// Validates that required properties are correctly implemented in the schema
+///
+/// # Arguments
+/// * `schema` - Reference to the parsed JsonSchema
+///
+/// # Errors
+/// - Emits an error for every required property that doesn't match the schema properties
+/// - Checks for:
+///   - Every required key exists in properties
+///   - Every property key is in the required
+pub fn check_properties_match_required(schema: &JsonSchema) {
+    let Some(properties) = schema.properties.as_ref() else {
+        return;
+    };
+
+    let Some((required, required_span)) =
+        schema.required.as_ref().zip(schema.required_span.as_ref())
+    else {
+        return;
+    };
+
+    for required_key in required {
+        if !properties.contains_key(required_key) {
+            emit_error!(
+                required_span.1,
+                "`{}` is required, but it's not in the properties",
+                required_key
+            );
+        }
+    }
+
+    for (property_key, property) in properties {
+        if !required.contains(property_key) {
+            emit_error!(
+                property.current_key_span.unwrap_or(required_span.0),
+                "`{}` is not in the required, make sure to implement all the required properties",
+                property_key
+            );
+        }
+    }
+}
+
+/// Performs comprehensive validation across different schema aspects
+///
+/// Runs a series of type-specific and structural validation checks
+///
+/// # Arguments
+/// * `schema` - Reference to the parsed JsonSchema
+///
+/// # Checks Performed
+/// - String type constraints
+/// - Number type constraints
+/// - Array type constraints
+/// - Object type constraints
+/// - Const value type
+/// - Constraint contradictions
+/// - Structural requirements
+pub fn validate_keys(schema: &JsonSchema) {
+    check_string_type(schema);
+    check_number_type(schema);
+    check_array_type(schema);
+    check_object_type(schema);
+    check_const_value(schema);
+    check_contradictions(schema);
+    other_checks(schema);
+}
+
+/// Validates that the `const` value matches the schema type
+///
+/// # Errors
+/// Emits errors if:
+/// - A string or char const is used with a non string type
+/// - A number const is used with a non number type
+/// - A const is used with an object or array type
+fn check_const_value(schema: &JsonSchema) {
+    let Some(const_value) = schema.const_value.as_ref() else {
+        return;
+    };
+
+    let matches_type = match const_value {
+        JsonSchemaValues::Str(_) | JsonSchemaValues::Char(_) => {
+            matches!(schema.ty, JsonSchemaTypes::String)
+        }
+        JsonSchemaValues::Number(num) => match schema.ty {
+            JsonSchemaTypes::Number => true,
+            // `2.5` isn't an integer, but `2.0` is
+            JsonSchemaTypes::Integer => num.as_f64().is_some_and(|num| num.fract() == 0.0),
+            _ => false,
+        },
+        _ => !matches!(schema.ty, JsonSchemaTypes::Object | JsonSchemaTypes::Array),
+    };
+
+    if !matches_type {
+        if let Some((_, value_span)) = schema.const_value_span {
+            emit_error!(
+                value_span,
+                "the `const` value doesn't match the `{}` type",
+                schema.ty
+            );
+        }
+    }
+}
+
+/// Validates that the lower and upper bounds of a constraint don't contradict each other
+///
+/// # Errors
+/// Emits errors if:
+/// - `min_length` is bigger than `max_length`
+/// - `minimum` is bigger than `maximum`
+/// - `min_items` is bigger than `max_items`
+fn check_contradictions(schema: &JsonSchema) {
+    fn check_bounds<T: PartialOrd + std::fmt::Display>(
+        (min, min_span): (Option<T>, Option<(Span, Span)>),
+        max: Option<T>,
+        (min_key, max_key): (&str, &str),
+    ) {
+        let Some((min, max)) = min.zip(max) else {
+            return;
+        };
+
+        if min > max {
+            emit_error!(
+                get_key_span(min_span),
+                "`{}` ({}) is bigger than `{}` ({}), nothing can match it",
+                min_key,
+                min,
+                max_key,
+                max
+            );
+        }
+    }
+
+    check_bounds(
+        (schema.min_lenght, schema.min_lenght_span),
+        schema.max_lenght,
+        ("min_length", "max_length"),
+    );
+
+    // a bound can be a float, they're compared as one
+    check_bounds(
+        (
+            schema.minimum.as_ref().and_then(Number::as_f64),
+            schema.minimum_span,
+        ),
+        schema.maximum.as_ref().and_then(Number::as_f64),
+        ("minimum", "maximum"),
+    );
+
+    check_bounds(
+        (schema.min_items, schema.min_items_span),
+        schema.max_items,
+        ("min_items", "max_items"),
+    );
+}
+
+/// Validates constraints for object-type schemas
+///
+/// # Errors
+/// Emits errors if object-specific keys are used with non-object types
+///
+/// Checks for incorrect usage of:
+/// - `required`
+/// - `properties`
+fn check_object_type(schema: &JsonSchema) {
+    fn report_error(span: Span, key: &str) {
+        emit_error!(span, "you can't use `{} in a non object type`", key);
+    }
+
+    if !matches!(schema.ty, JsonSchemaTypes::Object) {
+        if schema.required.is_some() {
+            report_error(get_key_span(schema.required_span), "required");
+        }
+
+        if schema.properties.is_some() {
+            report_error(get_key_span(schema.properties_span), "properties");
+        }
+    }
+}
+
+/// Validates constraints for array-type schemas
+///
+/// # Errors
+/// Emits errors if array-specific keys are used with non-array types
+///
+/// Checks for incorrect usage of:
+/// - `items`
+/// - `min_items`
+/// - `max_items`
+/// - `unique_items`
+/// - `contains`
+fn check_array_type(schema: &JsonSchema) {
+    fn report_error(span: Span, key: &str) {
+        emit_error!(span, "you can't use `{}` in a non array type", key);
+    }
+
+    if !matches!(schema.ty, JsonSchemaTypes::Array) {
+        if schema.items.is_some() {
+            report_error(get_key_span(schema.items_span), "items");
+        }
+
+        if schema.min_items.is_some() {
+            report_error(get_key_span(schema.min_items_span), "min_items");
+        }
+
+        if schema.max_items.is_some() {
+            report_error(get_key_span(schema.max_items_span), "max_items");
+        }
+
+        if schema.unique_items.is_some() {
+            report_error(get_key_span(schema.unique_items_span), "unique_items");
+        }
+
+        if schema.contains.is_some() {
+            report_error(get_key_span(schema.contains_span), "contains");
+        }
+    }
+}
+
+/// Validates constraints for number-type schemas
+///
+/// # Errors
+/// Emits errors if number-specific keys are used with non-number types
+///
+/// Checks for incorrect usage of:
+/// - `minimum`
+/// - `maximum`
+fn check_number_type(schema: &JsonSchema) {
+    fn report_error(span: Span, key: &str) {
+        emit_error!(span, "you can't use `{} in a non number type`", key);
+    }
+
+    if !matches!(
+        schema.ty,
+        JsonSchemaTypes::Number | JsonSchemaTypes::Integer
+    ) {
+        if schema.minimum.is_some() {
+            report_error(get_key_span(schema.minimum_span), "minimum");
+        }
+
+        if schema.maximum.is_some() {
+            report_error(get_key_span(schema.maximum_span), "maximum");
+        }
+    }
+}
+
+/// Validates constraints for string-type schemas
+///
+/// # Errors
+/// Emits errors if string-specific keys are used with non-string types
+///
+/// Checks for incorrect usage of:
+/// - `min_length`
+/// - `max_length`
+/// - `pattern`
+/// - `format`
+fn check_string_type(schema: &JsonSchema) {
+    fn report_error(span: Span, key: &str) {
+        emit_error!(span, "you can't use `{}` in a non string type", key);
+    }
+
+    if !matches!(schema.ty, JsonSchemaTypes::String) {
+        if schema.min_lenght.is_some() {
+            report_error(get_key_span(schema.min_lenght_span), "min_length");
+        }
+
+        if schema.max_lenght.is_some() {
+            report_error(get_key_span(schema.max_lenght_span), "max_length");
+        }
+
+        if schema.pattern.is_some() {
+            report_error(get_key_span(schema.pattern_span), "pattern");
+        }
+
+        if schema.format.is_some() {
+            report_error(get_key_span(schema.format_span), "format");
+        }
+    }
+}
+
+/// Retrieves the span for a given key
+///
+/// # Arguments
+/// * `have_span` - Optional tuple of spans
+///
+/// # Returns
+/// The first span from the tuple
+///
+/// # Panics
+/// If no span is available (indicates an internal bug)
+fn get_key_span(have_span: Option<(Span, Span)>) -> Span {
+    have_span
+        .expect("every key should have the span of it, this is a bug")
+        .0
+}
+
+/// Performs additional structural and semantic checks on the schema
+///
+/// # Checks Performed
+/// - Ensures `struct_name` is only used with object types
+/// - Validates root-level schema requirements
+/// - Prevents using reserved keywords
+/// - Ensures `derive`, `vis`, `mod` and `rename_all` are only used in the root schema
+///
+/// # Errors
+/// - Emits errors for structural violations and semantic inconsistencies
+pub fn other_checks(schema: &JsonSchema) {
+    if !matches!(schema.ty, JsonSchemaTypes::Object) && schema.struct_name.is_some() {
+        if let Some((struct_name_span, _)) = schema.struct_name_span {
+            emit_error!(
+                struct_name_span,
+                "`struct` is only allowed in an object type"
+            )
+        }
+    }
+
+    if schema.depth == 1 && schema.struct_name.is_none() {
+        if let Some((type_span, _)) = schema.ty_span {
+            emit_error!(
+                type_span,
+                "the first `struct` key is required, consider adding it"
+            );
+        }
+    }
+
+    // the macro-level options apply to every generated type, so they only make sense from the root
+    let nested_schemas = schema
+        .properties
+        .iter()
+        .flat_map(|properties| properties.values())
+        .chain(schema.items.as_deref());
+
+    for nested_schema in nested_schemas {
+        let root_only_keys = [
+            (nested_schema.derives_span, "derive"),
+            (nested_schema.vis_span, "vis"),
+            (nested_schema.module_span, "mod"),
+            (nested_schema.rename_all_span, "rename_all"),
+            (nested_schema.from_example_span, "from_example"),
+        ];
+
+        for (key_span, key) in root_only_keys {
+            if let Some((key_span, _)) = key_span {
+                emit_error!(key_span, "`{}` is only allowed in the root schema", key);
+            }
+        }
+    }
+
+    // Check if the struct_name exists and if depth is 1
+    if let Some(struct_name) = &schema.struct_name {
+        if schema.depth == 1 {
+            // If struct_name is "key", emit an error message
+            if struct_name == "key" {
+                if let Some((_, struct_span)) = schema.struct_name_span {
+                    emit_error!(struct_span, "you can't use `key` from the root schema");
+                }
+            }
+        }
+    }
+}
+
+/// Finds the closest candidate to a misspelled word, used for the "did you mean" suggestions
+///
+/// # Arguments
+/// * `word` - The misspelled word
+/// * `candidates` - The known words
+///
+/// # Returns
+/// The closest candidate, if it's close enough to be a typo
+pub fn closest_match<'a>(word: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let max_distance = (word.chars().count() / 3).max(1);
+
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, edit_distance(word, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+/// The edit distance between two words, where swapping two adjacent characters counts as a single edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // distances[i][j] is the distance between the first `i` chars of `a` and the first `j` chars of `b`
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -0,0 +1,678 @@
+/// Derive module
+///
+/// Builds a [`JsonSchema`] from a hand-written Rust struct, the reverse of the generator, so it gets
+/// the same `*_JSON_VALUE` constant and `HasJsonSchema` implementation as a generated one
+///
+/// It's behind both `#[derive(HasJsonSchema)]` and `#[schema2struct]`, the attribute also adds the
+/// serde derives, and leaves a missing `Option` field out instead of serializing a `null`
+///
+/// # Types
+/// - `String`, `str` and `char` are strings, the integers are integers and the floats are numbers
+/// - `Vec`, `VecDeque`, slices and arrays are arrays, `HashSet` and `BTreeSet` have unique items
+/// - `HashMap` and `BTreeMap` are objects with their values as `additionalProperties`, and
+///   `serde_json::Value` is any value
+/// - `Option` is nullable and not required, `Box`, `Rc`, `Arc` and references are their inner type
+/// - Any other type is embedded from it's own `HasJsonSchema` implementation
+///
+/// # Attributes
+/// - `#[schema(...)]` takes the same keywords as the macro, like `min_length = 3` or `format = "email"`
+/// - Doc comments are the `description`, unless there's one in `#[schema(...)]`
+/// - serde's `rename`, `rename_all`, `skip` and `default` are followed
+use std::collections::HashMap;
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde_json::Value;
+use syn::{ext::IdentExt as _, punctuated::Punctuated, spanned::Spanned as _, Token};
+
+use crate::{
+    checkers::{closest_match, validate_keys},
+    diagnostics,
+    generator::{get_serde_const, impl_has_json_schema, Options},
+    models::{JsonSchema, JsonSchemaTypes, RenameAll},
+    parsers::merge_keyword,
+};
+
+/// the keywords a field can have in `#[schema(...)]`
+const FIELD_KEYWORDS: [&str; 15] = [
+    "title",
+    "description",
+    "default",
+    "examples",
+    "enum",
+    "const",
+    "min_length",
+    "max_length",
+    "pattern",
+    "format",
+    "minimum",
+    "maximum",
+    "min_items",
+    "max_items",
+    "unique_items",
+];
+
+/// the keywords a struct can have in `#[schema(...)]`
+const STRUCT_KEYWORDS: [&str; 3] = ["title", "description", "examples"];
+
+/// A field type that isn't known, it's embedded from it's own `HasJsonSchema`
+struct NestedType {
+    ty: syn::Type,
+    nullable: bool,
+
+    // the JSON pointer of where it goes in the struct's schema, like `/properties/pets/items`
+    pointer: String,
+}
+
+/// Derives `HasJsonSchema` for a struct, along with it's `*_JSON_VALUE` constant
+///
+/// # Arguments
+/// * `tokens` - The struct the derive is on
+/// * `options` - The generation options, only `schemars` is used
+///
+/// # Errors
+/// - It's not a struct with named fields, or it's generic
+/// - Every invalid `#[schema(...)]` keyword, or one that doesn't fit the field type
+pub fn derive_has_json_schema(tokens: TokenStream, options: &Options) -> syn::Result<TokenStream> {
+    let input = syn::parse2::<syn::DeriveInput>(tokens)?;
+
+    schema_impl(&input, "`HasJsonSchema` can only be derived for", options)
+}
+
+/// Expands `#[schema2struct]`, the struct is kept, with the serde derives and attributes it needs,
+/// and gets the same items as `#[derive(HasJsonSchema)]`
+///
+/// # Arguments
+/// * `args` - The arguments of the attribute, there can't be any
+/// * `tokens` - The struct the attribute is on
+/// * `options` - The generation options, only `schemars` is used
+///
+/// # Errors
+/// - There are arguments, or it's not an item that can be derived
+///
+/// The errors of the schema are returned along with the struct, so it's uses don't fail too
+pub fn schema2struct_attr(
+    args: TokenStream,
+    tokens: TokenStream,
+    options: &Options,
+) -> syn::Result<TokenStream> {
+    if let Some(arg) = args.into_iter().next() {
+        return Err(syn::Error::new(
+            arg.span(),
+            "`#[schema2struct]` doesn't take arguments, use `#[schema(...)]` and `#[serde(...)]` on the struct",
+        ));
+    }
+
+    let mut input = syn::parse2::<syn::DeriveInput>(tokens)?;
+
+    // the struct is kept even when it's schema is broken, so it's uses don't fail along with it
+    let schema_impl = schema_impl(&input, "`#[schema2struct]` can only be used on", options)
+        .unwrap_or_else(|e| e.to_compile_error());
+
+    // there's no derive registering `#[schema]`, so it can't be left on the struct
+    let is_schema_attr = |attr: &syn::Attribute| attr.path().is_ident("schema");
+
+    input.attrs.retain(|attr| !is_schema_attr(attr));
+
+    if let syn::Data::Struct(data) = &mut input.data {
+        for field in data.fields.iter_mut() {
+            field.attrs.retain(|attr| !is_schema_attr(attr));
+
+            // the serde errors were already reported with the schema
+            let has_default = diagnostics::collect(|| Ok(SerdeAttrs::from_attrs(&field.attrs)))
+                .map_or(true, |serde| serde.default);
+
+            // a missing optional field is `None`, and it's left out instead of being `null`
+            if is_option(&field.ty) && !has_default {
+                field.attrs.push(syn::parse_quote! {
+                    #[serde(default, skip_serializing_if = "::std::option::Option::is_none")]
+                });
+            }
+        }
+    }
+
+    let missing_derives = missing_serde_derives(&input.attrs);
+
+    if !missing_derives.is_empty() {
+        input
+            .attrs
+            .insert(0, syn::parse_quote!(#[derive(#(#missing_derives),*)]));
+    }
+
+    Ok(quote! {
+        #input
+        #schema_impl
+    })
+}
+
+// the schema items of a struct, `what` starts the errors of the structs that can't have them
+fn schema_impl(
+    input: &syn::DeriveInput,
+    what: &str,
+    options: &Options,
+) -> syn::Result<TokenStream> {
+    let syn::Data::Struct(syn::DataStruct {
+        fields: syn::Fields::Named(fields),
+        ..
+    }) = &input.data
+    else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            format!("{} a struct with named fields", what),
+        ));
+    };
+
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            format!("{} a non-generic struct", what),
+        ));
+    }
+
+    diagnostics::collect(|| {
+        let mut nested_types = Vec::new();
+        let schema = struct_schema(&input.ident, &input.attrs, fields, &mut nested_types);
+
+        let schema_str = schema_str(&schema, &nested_types);
+
+        let mut output = get_serde_const(&input.ident, &input.vis);
+        output.extend(impl_has_json_schema(&input.ident, &schema_str, options));
+
+        Ok(output)
+    })
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    matches!(
+        ty,
+        syn::Type::Path(syn::TypePath { qself: None, path })
+            if path.segments.last().is_some_and(|segment| segment.ident == "Option")
+    )
+}
+
+// `Deserialize` and `Serialize`, unless they're derived already
+fn missing_serde_derives(attrs: &[syn::Attribute]) -> Vec<TokenStream> {
+    let derived = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("derive"))
+        .filter_map(|attr| {
+            attr.parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .filter_map(|path| {
+            path.segments
+                .last()
+                .map(|segment| segment.ident.to_string())
+        })
+        .collect::<Vec<_>>();
+
+    ["Deserialize", "Serialize"]
+        .into_iter()
+        .filter(|derive| !derived.iter().any(|derived| derived == derive))
+        .map(|derive| {
+            let derive = syn::Ident::new(derive, proc_macro2::Span::call_site());
+            quote!(::serde::#derive)
+        })
+        .collect()
+}
+
+// builds the object schema of the struct, with a property for every field that's (de)serialized
+fn struct_schema(
+    name: &syn::Ident,
+    attrs: &[syn::Attribute],
+    fields: &syn::FieldsNamed,
+    nested_types: &mut Vec<NestedType>,
+) -> JsonSchema {
+    let mut schema = JsonSchema {
+        ty: JsonSchemaTypes::Object,
+        struct_name: Some(name.to_string()),
+        depth: 1,
+        ..Default::default()
+    };
+
+    apply_schema_attrs(&mut schema, attrs, &STRUCT_KEYWORDS);
+
+    let container = SerdeAttrs::from_attrs(attrs);
+
+    let mut properties = IndexMap::new();
+    let mut required = Vec::new();
+
+    for field in &fields.named {
+        let serde = SerdeAttrs::from_attrs(&field.attrs);
+
+        if serde.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("the fields are named");
+        let field_name = ident.unraw().to_string();
+
+        let key = match (serde.rename, container.rename_all) {
+            (Some(rename), _) => rename,
+            (None, Some(rename_all)) => rename_all.apply(&field_name),
+            (None, None) => field_name,
+        };
+
+        let mut property = JsonSchema {
+            current_key_span: Some(ident.span()),
+            ..Default::default()
+        };
+
+        apply_schema_attrs(&mut property, &field.attrs, &FIELD_KEYWORDS);
+
+        let pointer = format!("/properties/{}", pointer_token(&key));
+        let type_schema = type_schema(&field.ty, &pointer, nested_types);
+        let nullable = type_schema.nullable == Some(true)
+            || nested_at(nested_types, &pointer).is_some_and(|nested| nested.nullable);
+
+        fill_type(&mut property, type_schema);
+        validate_keys(&property);
+
+        if !nullable && !serde.default && !container.default {
+            required.push(key.clone());
+        }
+
+        properties.insert(key, property);
+    }
+
+    schema.properties = Some(properties);
+    schema.required = Some(required);
+
+    schema
+}
+
+// puts what the field type says into the property, the `#[schema(...)]` values come first
+fn fill_type(property: &mut JsonSchema, type_schema: JsonSchema) {
+    property.ty = type_schema.ty;
+    property.items = type_schema.items;
+    property.additional_properties = type_schema.additional_properties;
+    property.nullable = type_schema.nullable;
+    property.unique_items = property.unique_items.or(type_schema.unique_items);
+    property.minimum = property.minimum.take().or(type_schema.minimum);
+}
+
+// maps a Rust type to it's schema, the unknown ones are left empty and their schema is put at
+// `pointer` at compile time
+fn type_schema(ty: &syn::Type, pointer: &str, nested_types: &mut Vec<NestedType>) -> JsonSchema {
+    let of_type = |ty| JsonSchema {
+        ty,
+        ..Default::default()
+    };
+
+    let array_of = |items: JsonSchema| JsonSchema {
+        ty: JsonSchemaTypes::Array,
+        items: Some(Box::new(items)),
+        ..Default::default()
+    };
+
+    let items = format!("{}/items", pointer);
+
+    let path = match ty {
+        syn::Type::Reference(reference) => {
+            return type_schema(&reference.elem, pointer, nested_types)
+        }
+        syn::Type::Paren(paren) => return type_schema(&paren.elem, pointer, nested_types),
+        syn::Type::Group(group) => return type_schema(&group.elem, pointer, nested_types),
+        syn::Type::Slice(slice) => return array_of(type_schema(&slice.elem, &items, nested_types)),
+        syn::Type::Array(array) => return array_of(type_schema(&array.elem, &items, nested_types)),
+        syn::Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return nested_type(ty, pointer, nested_types),
+    };
+
+    let Some(segment) = path.segments.last() else {
+        return nested_type(ty, pointer, nested_types);
+    };
+
+    // the type arguments, like the `T` in `Vec<T>` or the `K` and `V` in `HashMap<K, V>`
+    let arguments = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(arguments) => arguments
+            .args
+            .iter()
+            .filter_map(|argument| match argument {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    let inner = arguments.first().copied();
+
+    match (segment.ident.to_string().as_str(), inner) {
+        ("String" | "str" | "char", _) => of_type(JsonSchemaTypes::String),
+        ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", _) => of_type(JsonSchemaTypes::Integer),
+        ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", _) => JsonSchema {
+            minimum: Some(0.into()),
+            ..of_type(JsonSchemaTypes::Integer)
+        },
+        ("f32" | "f64", _) => of_type(JsonSchemaTypes::Number),
+        ("bool", _) => of_type(JsonSchemaTypes::Boolean),
+        ("Value", _) => JsonSchema::default(),
+        ("HashMap" | "BTreeMap" | "IndexMap", _) => JsonSchema {
+            additional_properties: arguments.get(1).map(|values| {
+                let pointer = format!("{}/additionalProperties", pointer);
+                Box::new(type_schema(values, &pointer, nested_types))
+            }),
+            ..of_type(JsonSchemaTypes::Object)
+        },
+
+        ("Option", Some(inner)) => {
+            let mut schema = type_schema(inner, pointer, nested_types);
+
+            match nested_at(nested_types, pointer) {
+                Some(nested) => nested.nullable = true,
+                None => schema.nullable = Some(true),
+            }
+
+            schema
+        }
+        ("Box" | "Rc" | "Arc" | "Cow", Some(inner)) => type_schema(inner, pointer, nested_types),
+        ("Vec" | "VecDeque" | "LinkedList", Some(inner)) => {
+            array_of(type_schema(inner, &items, nested_types))
+        }
+        ("HashSet" | "BTreeSet" | "IndexSet", Some(inner)) => JsonSchema {
+            unique_items: Some(true),
+            ..array_of(type_schema(inner, &items, nested_types))
+        },
+
+        _ => nested_type(ty, pointer, nested_types),
+    }
+}
+
+// an unknown type, it's schema is empty until it's embedded
+fn nested_type(ty: &syn::Type, pointer: &str, nested_types: &mut Vec<NestedType>) -> JsonSchema {
+    nested_types.push(NestedType {
+        ty: ty.clone(),
+        nullable: false,
+        pointer: pointer.to_owned(),
+    });
+
+    JsonSchema::default()
+}
+
+fn nested_at<'a>(nested_types: &'a mut [NestedType], pointer: &str) -> Option<&'a mut NestedType> {
+    nested_types
+        .iter_mut()
+        .find(|nested| nested.pointer == pointer)
+}
+
+// a key escaped for a JSON pointer, `~` is `~0` and `/` is `~1`
+fn pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+// reads the `#[schema(...)]` keywords, and the doc comments as the description
+fn apply_schema_attrs(schema: &mut JsonSchema, attrs: &[syn::Attribute], allowed: &[&str]) {
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("schema")) {
+        let pairs = match attr
+            .parse_args_with(Punctuated::<syn::MetaNameValue, Token![,]>::parse_terminated)
+        {
+            Ok(pairs) => pairs,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                continue;
+            }
+        };
+
+        for pair in pairs {
+            let Some(key) = pair.path.get_ident() else {
+                emit_error!(pair.path.span(), "expected a keyword, like `min_length`");
+                continue;
+            };
+
+            let key_str = key.to_string();
+
+            if !allowed.contains(&key_str.as_str()) {
+                match closest_match(&key_str, allowed) {
+                    Some(suggestion) => emit_error!(
+                        key.span(),
+                        "`{}` can't be used here, did you mean `{}`?",
+                        key_str,
+                        suggestion
+                    ),
+                    None => emit_error!(key.span(), "`{}` can't be used here", key_str),
+                }
+                continue;
+            }
+
+            let value_span = pair.value.span();
+
+            match JsonSchema::try_from((key.clone(), pair.value)) {
+                Ok(value) => merge_keyword(schema, value, key.span(), value_span),
+                Err(e) => emit_error!(e.span(), e),
+            }
+        }
+    }
+
+    if schema.description.is_none() {
+        let (lines, spans): (Vec<_>, Vec<_>) = attrs
+            .iter()
+            .filter_map(|attr| match &attr.meta {
+                syn::Meta::NameValue(syn::MetaNameValue {
+                    path,
+                    value:
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(doc),
+                            ..
+                        }),
+                    ..
+                }) if path.is_ident("doc") => Some((doc.value(), attr.span())),
+                _ => None,
+            })
+            .unzip();
+
+        if let Some(span) = spans.first() {
+            let description = lines
+                .iter()
+                .map(|line| line.strip_prefix(' ').unwrap_or(line))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            schema.description = Some(description.trim().to_owned());
+            schema.description_span = Some((*span, *span));
+        }
+    }
+}
+
+/// The serde attributes that change the schema
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<RenameAll>,
+    skip: bool,
+    default: bool,
+}
+
+impl SerdeAttrs {
+    fn from_attrs(attrs: &[syn::Attribute]) -> Self {
+        let mut serde = Self::default();
+
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let result = attr.parse_nested_meta(|meta| {
+                // `rename = "..."`, or `rename(serialize = "...", deserialize = "...")`
+                let serialized_name =
+                    |meta: syn::meta::ParseNestedMeta| -> syn::Result<Option<syn::LitStr>> {
+                        if meta.input.peek(Token![=]) {
+                            return Ok(Some(meta.value()?.parse::<syn::LitStr>()?));
+                        }
+
+                        let mut name = None;
+
+                        meta.parse_nested_meta(|meta| {
+                            let value = meta.value()?.parse::<syn::LitStr>()?;
+
+                            if meta.path.is_ident("serialize") {
+                                name = Some(value);
+                            }
+
+                            Ok(())
+                        })?;
+
+                        Ok(name)
+                    };
+
+                if meta.path.is_ident("rename") {
+                    serde.rename = serialized_name(meta)?.map(|name| name.value());
+                } else if meta.path.is_ident("rename_all") {
+                    if let Some(rule) = serialized_name(meta)? {
+                        serde.rename_all = Some(RenameAll::try_from(rule)?);
+                    }
+                } else if meta.path.is_ident("skip") {
+                    serde.skip = true;
+                } else if meta.path.is_ident("default") {
+                    serde.default = true;
+                    skip_meta_value(&meta)?;
+                } else if meta.path.is_ident("flatten") {
+                    return Err(meta.error("`flatten` isn't supported by `HasJsonSchema`"));
+                } else {
+                    skip_meta_value(&meta)?;
+                }
+
+                Ok(())
+            });
+
+            if let Err(e) = result {
+                emit_error!(e.span(), e);
+            }
+        }
+
+        serde
+    }
+}
+
+// the other serde attributes don't matter, but their values still have to be parsed
+fn skip_meta_value(meta: &syn::meta::ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        meta.value()?.parse::<syn::Expr>()?;
+    } else if meta.input.peek(syn::token::Paren) {
+        let content;
+        syn::parenthesized!(content in meta.input);
+        content.parse::<TokenStream>()?;
+    }
+
+    Ok(())
+}
+
+// the schema as a const string expression, the nested types are embedded from their own
+// `SCHEMA_STR` at compile time
+fn schema_str(schema: &JsonSchema, nested_types: &[NestedType]) -> TokenStream {
+    let mut value = schema.to_json_schema();
+
+    if nested_types.is_empty() {
+        let json = value.to_string();
+        return quote!(#json);
+    }
+
+    let splices = nested_types
+        .iter()
+        .map(|nested_type| (make_room(&mut value, nested_type), &nested_type.ty))
+        .collect::<HashMap<_, _>>();
+
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+
+    write_parts(
+        &value,
+        &mut String::new(),
+        &splices,
+        &mut literal,
+        &mut parts,
+    );
+
+    parts.push(quote!(::schema2struct::__private::Part::Str(#literal)));
+
+    quote! {
+        {
+            const PARTS: &[::schema2struct::__private::Part] = &[#(#parts),*];
+            const BYTES: [u8; ::schema2struct::__private::len(PARTS)] =
+                ::schema2struct::__private::concat(PARTS);
+
+            match ::std::str::from_utf8(&BYTES) {
+                ::std::result::Result::Ok(schema) => schema,
+                ::std::result::Result::Err(_) => ::std::panic!("a schema is always valid UTF-8"),
+            }
+        }
+    }
+}
+
+// makes room for the schema of a nested type, and returns the pointer of where it goes
+//
+// it takes the place of the empty schema, it goes in an `allOf` when the field adds something to it,
+// like a `description`, and a nullable one is in an `anyOf` along with `null`
+fn make_room(value: &mut Value, nested_type: &NestedType) -> String {
+    let Some(Value::Object(object)) = value.pointer_mut(&nested_type.pointer) else {
+        unreachable!("every nested type has an object schema at it's pointer")
+    };
+
+    let (key, schemas) = match (object.is_empty(), nested_type.nullable) {
+        (true, false) => return nested_type.pointer.clone(),
+        (_, true) => ("anyOf", serde_json::json!([{}, { "type": "null" }])),
+        (false, false) => ("allOf", serde_json::json!([{}])),
+    };
+
+    object.insert(key.into(), schemas);
+
+    format!("{}/{}/0", nested_type.pointer, key)
+}
+
+// writes the value as compact JSON into `literal`, a splice ends it and embeds the `SCHEMA_STR` of
+// it's type instead of the value at it's pointer
+fn write_parts(
+    value: &Value,
+    pointer: &mut String,
+    splices: &HashMap<String, &syn::Type>,
+    literal: &mut String,
+    parts: &mut Vec<TokenStream>,
+) {
+    if let Some(ty) = splices.get(pointer.as_str()) {
+        let before = std::mem::take(literal);
+
+        parts.push(quote!(::schema2struct::__private::Part::Str(#before)));
+        parts.push(quote! {
+            ::schema2struct::__private::Part::Schema(<#ty as ::schema2struct::HasJsonSchema>::SCHEMA_STR)
+        });
+
+        return;
+    }
+
+    let len = pointer.len();
+
+    match value {
+        Value::Object(object) => {
+            literal.push('{');
+
+            for (i, (key, value)) in object.iter().enumerate() {
+                if i > 0 {
+                    literal.push(',');
+                }
+
+                literal.push_str(&Value::from(key.as_str()).to_string());
+                literal.push(':');
+
+                pointer.push('/');
+                pointer.push_str(&pointer_token(key));
+                write_parts(value, pointer, splices, literal, parts);
+                pointer.truncate(len);
+            }
+
+            literal.push('}');
+        }
+        Value::Array(array) => {
+            literal.push('[');
+
+            for (i, value) in array.iter().enumerate() {
+                if i > 0 {
+                    literal.push(',');
+                }
+
+                pointer.push_str(&format!("/{}", i));
+                write_parts(value, pointer, splices, literal, parts);
+                pointer.truncate(len);
+            }
+
+            literal.push(']');
+        }
+        value => literal.push_str(&value.to_string()),
+    }
+}
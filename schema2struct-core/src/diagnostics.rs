@@ -0,0 +1,60 @@
+/// Diagnostics module for collecting every schema error
+///
+/// The parser and the checkers don't stop at the first problem, they emit it with
+/// [`emit_error!`] and go on, then [`collect`] gathers all of them into a single error
+///
+/// # Example
+/// ```ignore
+/// let schema = diagnostics::collect(|| syn::parse2::<JsonSchema>(tokens))?;
+/// ```
+use std::cell::RefCell;
+
+thread_local! {
+    /// the errors emitted by the current [`collect`] call
+    static DIAGNOSTICS: RefCell<Vec<syn::Error>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Emits an error without stopping, it gets reported once the current [`collect`] call ends
+///
+/// ```ignore
+/// emit_error!(span, "you can't use `{}` in a non string type", key);
+/// emit_error!(e.span(), e);
+/// ```
+macro_rules! emit_error {
+    ($span:expr, $message:literal $(,)?) => {
+        $crate::diagnostics::emit(::syn::Error::new($span, $message))
+    };
+    ($span:expr, $format:literal, $($args:tt)+) => {
+        $crate::diagnostics::emit(::syn::Error::new($span, format!($format, $($args)+)))
+    };
+    ($span:expr, $error:expr $(,)?) => {
+        $crate::diagnostics::emit(::syn::Error::new($span, $error))
+    };
+}
+
+pub(crate) fn emit(error: syn::Error) {
+    DIAGNOSTICS.with(|diagnostics| diagnostics.borrow_mut().push(error));
+}
+
+/// Runs `f` and collects every error emitted while it runs
+///
+/// # Returns
+/// The result of `f`, or a single error combining every emitted one along with the one `f` returned
+pub(crate) fn collect<T>(f: impl FnOnce() -> syn::Result<T>) -> syn::Result<T> {
+    // keeps the errors of an outer call apart
+    let outer = DIAGNOSTICS.with(|diagnostics| diagnostics.take());
+
+    let result = f();
+
+    let emitted = DIAGNOSTICS.with(|diagnostics| diagnostics.replace(outer));
+
+    let errors = emitted.into_iter().chain(result.as_ref().err().cloned());
+
+    match errors.reduce(|mut all, error| {
+        all.combine(error);
+        all
+    }) {
+        Some(error) => Err(error),
+        None => result,
+    }
+}
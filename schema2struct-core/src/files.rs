@@ -0,0 +1,981 @@
+/// Schema files module
+///
+/// Reads JSON Schema files into the same [`JsonSchema`] model the macro parses,
+/// so they go through the same generator
+///
+/// # Formats
+/// A file is read by it's extension, `.yaml` and `.yml` are YAML, `.toml` is TOML and anything
+/// else is JSON, they're all the same schema written differently
+///
+/// A file named like `event.jtd.json` is a JSON Type Definition instead, and a `.avsc` file is an
+/// Avro schema, they're lowered into the same model
+///
+/// # Dialect
+/// - `$ref`s to `#/$defs/...` and `#/definitions/...` are resolved, a referenced object is named
+///   after it's definition
+/// - The objects of an `allOf` are merged into a single one
+/// - A `["string", "null"]` type becomes an `Option`
+/// - A property is an `Option` unless it's `required`, without a `required` none of them is
+/// - The keywords the model can't hold, like an `int64` format, are left out
+///
+/// # Naming
+/// The root struct is named after the schema `title`, or the file name if there's no title
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde::de::DeserializeOwned;
+use syn::{
+    ext::IdentExt as _,
+    parse::{Parse, ParseStream},
+    spanned::Spanned as _,
+    Token,
+};
+
+use crate::{
+    avro,
+    checkers::closest_match,
+    generator::{generate, Options},
+    jtd,
+    models::{Discriminator, Formats, JsonSchema, JsonSchemaTypes, JsonSchemaValues, RenameAll},
+    naming::type_ident,
+    parsers,
+};
+
+/// An error from reading a schema file, it always knows which file it came from
+#[derive(Debug)]
+pub struct FileError {
+    pub path: PathBuf,
+    pub kind: FileErrorKind,
+}
+
+#[derive(Debug)]
+pub enum FileErrorKind {
+    /// the file couldn't be read
+    Io(std::io::Error),
+
+    /// the file isn't a valid schema, the position is 1-based
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+
+    /// the file is valid, but something in it can't be used, like a missing component
+    Invalid { location: String, message: String },
+}
+
+impl std::fmt::Display for FileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            FileErrorKind::Io(e) => write!(f, "{}: {}", self.path.display(), e),
+            FileErrorKind::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "{}:{}:{}: {}",
+                self.path.display(),
+                line,
+                column,
+                message
+            ),
+            FileErrorKind::Invalid { location, message } => {
+                write!(f, "{}: {}: {}", self.path.display(), location, message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FileError {}
+
+/// The formats a file can be written in
+#[derive(Clone, Copy)]
+enum Format {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl Format {
+    // by the extension of the file, it's JSON if it's not a known one
+    fn of(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("yaml" | "yml") => Self::Yaml,
+            Some("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    // deserializes the whole file, the error has the 1-based position of the problem
+    fn parse<T: DeserializeOwned>(self, content: &str) -> Result<T, FileErrorKind> {
+        let (line, column, message) = match self {
+            Self::Json => match serde_json::from_str(content) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => (e.line(), e.column(), e.to_string()),
+            },
+            Self::Yaml => match serde_yaml::from_str(content) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => {
+                    let (line, column) = e
+                        .location()
+                        .map_or((1, 1), |location| (location.line(), location.column()));
+
+                    (line, column, e.to_string())
+                }
+            },
+            // the TOML error is a whole report with a snippet, only the message is kept
+            Self::Toml => match toml::from_str(content) {
+                Ok(parsed) => return Ok(parsed),
+                Err(e) => {
+                    let (line, column) = e
+                        .span()
+                        .map_or((1, 1), |span| line_column(content, span.start));
+
+                    (line, column, e.message().to_owned())
+                }
+            },
+        };
+
+        Err(FileErrorKind::Parse {
+            line,
+            column,
+            message,
+        })
+    }
+}
+
+// the 1-based line and column of a byte offset
+fn line_column(content: &str, offset: usize) -> (usize, usize) {
+    let before = content.get(..offset).unwrap_or(content);
+
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+
+    (line, column)
+}
+
+fn read_file(path: &Path) -> Result<String, FileError> {
+    std::fs::read_to_string(path).map_err(|e| FileError {
+        path: path.to_owned(),
+        kind: FileErrorKind::Io(e),
+    })
+}
+
+/// Reads a JSON Schema file, written in JSON, YAML or TOML by it's extension, or a JSON Type
+/// Definition or an Avro schema
+///
+/// # Arguments
+/// * `path` - The path of the schema file
+///
+/// # Errors
+/// - The file can't be read
+/// - The file isn't a valid schema, or it's root isn't an object
+pub fn read_schema_file(path: &Path) -> Result<JsonSchema, FileError> {
+    let content = read_file(path)?;
+    let format = Format::of(path);
+
+    let error = |kind| FileError {
+        path: path.to_owned(),
+        kind,
+    };
+
+    let invalid = |location: String, message| {
+        error(FileErrorKind::Invalid {
+            location: match location.is_empty() {
+                true => "root".to_owned(),
+                false => location,
+            },
+            message,
+        })
+    };
+
+    // a JSON Type Definition and an Avro schema are lowered into the same model
+    let mut schema: JsonSchema = if jtd_stem(path).is_some() {
+        let document = format.parse(&content).map_err(error)?;
+
+        jtd::lower(&document).map_err(|e| invalid(e.location, e.message))?
+    } else if is_avro(path) {
+        let document = format.parse(&content).map_err(error)?;
+
+        avro::lower(&document).map_err(|e| invalid(e.location, e.message))?
+    } else {
+        let mut document = format.parse::<serde_json::Value>(&content).map_err(error)?;
+
+        let mut definitions = IndexMap::new();
+
+        for key in ["$defs", "definitions"] {
+            let Some(serde_json::Value::Object(schemas)) = document
+                .as_object_mut()
+                .and_then(|document| document.shift_remove(key))
+            else {
+                continue;
+            };
+
+            for (name, schema) in schemas {
+                let schema = parse_schema(schema)
+                    .map_err(|e| invalid(format!("{}.{}", key, name), e.to_string()))?;
+
+                definitions.insert(name, schema);
+            }
+        }
+
+        // the errors keep their position from the file as it's written, when it has one
+        let mut schema =
+            parse_schema(document).map_err(|e| match format.parse::<JsonSchema>(&content) {
+                Ok(_) => invalid(String::new(), e.to_string()),
+                Err(kind) => error(kind),
+            })?;
+
+        Definitions {
+            schemas: &definitions,
+            prefixes: &["#/$defs/", "#/definitions/"],
+            place: "the `$defs`",
+        }
+        .resolve(&mut schema, &mut Vec::new())
+        .map_err(|message| invalid(String::new(), message))?;
+
+        schema
+    };
+
+    if !matches!(schema.ty, JsonSchemaTypes::Object) {
+        return Err(invalid(
+            String::new(),
+            "the root schema must be an object".to_owned(),
+        ));
+    }
+
+    let name = schema.title.clone().unwrap_or_else(|| {
+        jtd_stem(path)
+            .or_else(|| {
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned())
+            })
+            .unwrap_or_default()
+    });
+
+    schema.struct_name = Some(type_ident(None, &name).to_string());
+    schema.depth = 1;
+
+    Ok(schema)
+}
+
+// the name of a JSON Type Definition file, it's `event.jtd.json` for `event`
+fn jtd_stem(path: &Path) -> Option<String> {
+    let stem = path.file_stem()?.to_str()?;
+
+    stem.strip_suffix(".jtd").map(ToOwned::to_owned)
+}
+
+// an Avro schema, it's always written in JSON
+fn is_avro(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "avsc")
+}
+
+/// Reads a document written in JSON, YAML or TOML by it's extension
+///
+/// # Errors
+/// - The file can't be read
+/// - The file isn't valid, with the position of the problem
+pub(crate) fn read_document(path: &Path) -> Result<serde_json::Value, FileError> {
+    let content = read_file(path)?;
+
+    Format::of(path).parse(&content).map_err(|kind| FileError {
+        path: path.to_owned(),
+        kind,
+    })
+}
+
+/// Resolves a path from the macro input, it's relative to the crate's `Cargo.toml`
+pub(crate) fn manifest_path(path: &syn::LitStr) -> PathBuf {
+    std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join(path.value())
+}
+
+/// the generation options every file input takes, the same ones as the DSL
+const OPTION_KEYS: [&str; 5] = ["derive", "attrs", "vis", "mod", "rename_all"];
+
+/// The generation options of a file input, like `derive = [PartialEq]` or `vis = pub(crate)`
+#[derive(Default)]
+pub struct InputOptions {
+    pub derives: Vec<syn::Path>,
+    pub attrs: Vec<syn::Attribute>,
+    pub vis: Option<syn::Visibility>,
+    pub module: Option<syn::Ident>,
+    pub rename_all: Option<RenameAll>,
+}
+
+impl InputOptions {
+    /// The options to generate with, the derives and the attributes are added to the given ones,
+    /// and the rest replace them
+    pub fn apply(self, options: &Options) -> Options {
+        Options {
+            derives: options
+                .derives
+                .iter()
+                .cloned()
+                .chain(self.derives)
+                .collect(),
+            attrs: options.attrs.iter().cloned().chain(self.attrs).collect(),
+            vis: self.vis.unwrap_or_else(|| options.vis.clone()),
+            module: self.module.or_else(|| options.module.clone()),
+            rename_all: self.rename_all.or(options.rename_all),
+            ..options.clone()
+        }
+    }
+}
+
+/// Parses the `key = value` pairs of a file input, like `file = "user.yaml", derive = [PartialEq]`
+///
+/// the generation options are parsed here, every other key goes to `parse_key` with the input
+/// right after it's `=`
+///
+/// # Errors
+/// - A key isn't one of the `keys` or the options
+/// - A value is invalid
+pub(crate) fn parse_input(
+    input: ParseStream,
+    keys: &[&str],
+    mut parse_key: impl FnMut(&syn::Ident, ParseStream) -> syn::Result<()>,
+) -> syn::Result<InputOptions> {
+    let mut options = InputOptions::default();
+
+    while !input.is_empty() {
+        // `mod` is a keyword
+        let key = input.call(syn::Ident::parse_any)?;
+        input.parse::<Token![=]>()?;
+
+        match key.to_string().as_str() {
+            "derive" => options.derives = parsers::handle_derives(&input)?.derives,
+            "attrs" => options.attrs = parsers::handle_attrs(&input)?.attrs,
+            // `pub(crate)` is not an expression, so it can't go through `syn::Expr`
+            "vis" => options.vis = Some(input.parse()?),
+            "mod" => options.module = Some(input.parse()?),
+            "rename_all" => {
+                options.rename_all = Some(RenameAll::try_from(parsers::handle_rename_all(&input)?)?)
+            }
+            name if keys.contains(&name) => parse_key(&key, input)?,
+            name => {
+                let known = keys.iter().chain(&OPTION_KEYS).copied().collect::<Vec<_>>();
+
+                let message = match closest_match(name, &known) {
+                    Some(suggestion) => {
+                        format!("unknown key `{}`, did you mean `{}`?", name, suggestion)
+                    }
+                    None => format!(
+                        "unknown key `{}`, expected one of `{}`",
+                        name,
+                        known.join("`, `")
+                    ),
+                };
+
+                return Err(syn::Error::new(key.span(), message));
+            }
+        }
+
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parses a string value of a file input, like it's path, `message` says what it should be
+pub(crate) fn string_value(input: ParseStream, message: &str) -> syn::Result<syn::LitStr> {
+    match input.parse::<syn::Expr>()? {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Ok(lit),
+        value => Err(syn::Error::new(value.span(), message)),
+    }
+}
+
+/// Makes the generated types get rebuilt when the file they're from changes
+pub(crate) fn rebuilt_on_change(path: &Path, output: TokenStream) -> TokenStream {
+    let path = path.to_string_lossy();
+
+    quote! {
+        const _: &[u8] = ::std::include_bytes!(#path);
+
+        #output
+    }
+}
+
+/// The input of `schema2struct!(file = "schemas/user.yaml", derive = [PartialEq])`
+pub struct FileInput {
+    pub path: syn::LitStr,
+    pub options: InputOptions,
+}
+
+impl Parse for FileInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path = None;
+
+        let options = parse_input(input, &["file"], |_, input| {
+            path = Some(string_value(
+                input,
+                "`file` is the path of the schema, like `\"schemas/user.yaml\"`",
+            )?);
+
+            Ok(())
+        })?;
+
+        let Some(path) = path else {
+            return Err(input.error("`file = \"...\"` is required"));
+        };
+
+        Ok(Self { path, options })
+    }
+}
+
+/// Whether the macro input is a schema file, it starts with `file =`
+pub fn is_file_input(tokens: &TokenStream) -> bool {
+    let mut tokens = tokens.clone().into_iter();
+
+    matches!(
+        (tokens.next(), tokens.next()),
+        (
+            Some(proc_macro2::TokenTree::Ident(ident)),
+            Some(proc_macro2::TokenTree::Punct(punct)),
+        ) if ident == "file" && punct.as_char() == '='
+    )
+}
+
+/// Generates the types of a schema file, the path is relative to the crate's `Cargo.toml`
+///
+/// # Errors
+/// - The input is invalid
+/// - The file can't be read or parsed, the error has it's path, line and column
+/// - The types can't be generated
+pub fn expand_file(tokens: TokenStream, options: &Options) -> syn::Result<TokenStream> {
+    let input = syn::parse2::<FileInput>(tokens)?;
+    let path = manifest_path(&input.path);
+
+    let schema = read_schema_file(&path).map_err(|e| syn::Error::new(input.path.span(), e))?;
+
+    let output = generate(&schema, &input.options.apply(options))?;
+
+    Ok(rebuilt_on_change(&path, output))
+}
+
+// turns a list of types into a single one, with `null` becoming the `nullable` flag
+//
+// more than one type, other than `null`, can't be a single Rust type, so it's left as any value
+pub(crate) fn normalize_types(schema: &mut serde_json::Value) {
+    let Some(schema) = schema.as_object_mut() else {
+        return;
+    };
+
+    if let Some(serde_json::Value::Array(types)) = schema.get("type") {
+        let nullable = types.iter().any(|ty| ty == "null");
+        let types = types
+            .iter()
+            .filter(|ty| *ty != "null")
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match <[_; 1]>::try_from(types) {
+            Ok([ty]) => schema["type"] = ty,
+            Err(_) => {
+                schema.shift_remove("type");
+            }
+        }
+
+        if nullable {
+            schema.insert("nullable".into(), true.into());
+        }
+    }
+
+    if let Some(serde_json::Value::Object(properties)) = schema.get_mut("properties") {
+        properties.values_mut().for_each(normalize_types);
+    }
+
+    for key in ["items", "contains", "additionalProperties"] {
+        if let Some(nested) = schema.get_mut(key) {
+            normalize_types(nested);
+        }
+    }
+
+    for key in ["oneOf", "allOf"] {
+        if let Some(serde_json::Value::Array(schemas)) = schema.get_mut(key) {
+            schemas.iter_mut().for_each(normalize_types);
+        }
+    }
+}
+
+// reads a schema, after dropping and normalizing what the model can't hold
+pub(crate) fn parse_schema(mut schema: serde_json::Value) -> Result<JsonSchema, serde_json::Error> {
+    lower(&mut schema);
+    normalize_types(&mut schema);
+
+    serde_json::from_value(schema)
+}
+
+fn fits<T: DeserializeOwned>(value: &serde_json::Value) -> bool {
+    serde_json::from_value::<T>(value.clone()).is_ok()
+}
+
+// drops the keywords the model can't hold, a schema is written for other tools too
+fn lower(schema: &mut serde_json::Value) {
+    let Some(schema) = schema.as_object_mut() else {
+        return;
+    };
+
+    schema.retain(|key, value| match key.as_str() {
+        "format" => fits::<Formats>(value),
+        "minimum" | "maximum" => value.is_number(),
+        "minLength" | "maxLength" | "minItems" | "maxItems" => value.is_u64(),
+        "default" | "const" => fits::<JsonSchemaValues>(value),
+        "enum" => fits::<Vec<JsonSchemaValues>>(value),
+        "examples" => value.is_array(),
+        _ => true,
+    });
+
+    // a property is optional unless it's `required`, it's not the other way around like in the macro
+    if schema.contains_key("properties") && !schema.contains_key("required") {
+        schema.insert("required".into(), serde_json::Value::Array(Vec::new()));
+    }
+
+    if let Some(serde_json::Value::Object(properties)) = schema.get_mut("properties") {
+        properties.values_mut().for_each(lower);
+    }
+
+    for key in ["items", "contains", "additionalProperties"] {
+        if let Some(nested) = schema.get_mut(key) {
+            lower(nested);
+        }
+    }
+
+    for key in ["oneOf", "allOf"] {
+        if let Some(serde_json::Value::Array(schemas)) = schema.get_mut(key) {
+            schemas.iter_mut().for_each(lower);
+        }
+    }
+}
+
+/// The schemas a `$ref` can point to, the `$defs` of a file or the components of an OpenAPI
+/// document
+pub(crate) struct Definitions<'a> {
+    pub schemas: &'a IndexMap<String, JsonSchema>,
+
+    /// what a reference to them starts with, like `#/$defs/`
+    pub prefixes: &'a [&'a str],
+
+    /// where they are, for the errors
+    pub place: &'a str,
+}
+
+impl Definitions<'_> {
+    /// Replaces every reference with the schema it points to, `stack` is the schemas being
+    /// resolved
+    ///
+    /// the members of an `allOf` are merged into it once they're resolved
+    ///
+    /// # Errors
+    /// - A reference doesn't start with one of the prefixes, or there's no such schema
+    /// - A reference is recursive
+    /// - A member of an `allOf` isn't an object
+    pub fn resolve(&self, schema: &mut JsonSchema, stack: &mut Vec<String>) -> Result<(), String> {
+        if let Some(mut members) = schema.all_of.take() {
+            for member in &mut members {
+                self.resolve(member, stack)?;
+            }
+
+            merge_all_of(schema, members)?;
+        }
+
+        if let Some(reference) = schema.reference.take() {
+            let name = self.reference_name(&reference)?;
+
+            if stack.iter().any(|resolving| resolving == name) {
+                return Err(format!(
+                    "`{}` references itself through `{}`, recursive types aren't supported",
+                    name,
+                    stack.join("` -> `")
+                ));
+            }
+
+            let mut resolved = self.schemas[name].clone();
+
+            stack.push(name.to_owned());
+            self.resolve(&mut resolved, stack)?;
+            stack.pop();
+
+            if matches!(resolved.ty, JsonSchemaTypes::Object) || resolved.one_of.is_some() {
+                resolved.struct_name = Some(name.to_owned());
+            }
+
+            // what's next to the `$ref` still applies, like the `nullable` of OpenAPI 3.0
+            resolved.nullable = schema.nullable.or(resolved.nullable);
+            resolved.description = schema.description.take().or(resolved.description);
+
+            *schema = resolved;
+
+            return Ok(());
+        }
+
+        for property in schema.properties.iter_mut().flat_map(|p| p.values_mut()) {
+            self.resolve(property, stack)?;
+        }
+
+        for nested in [
+            &mut schema.items,
+            &mut schema.contains,
+            &mut schema.additional_properties,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.resolve(nested, stack)?;
+        }
+
+        for variant in schema.one_of.iter_mut().flatten() {
+            // the tag is known from the reference, it's gone once it's resolved
+            let tag = schema
+                .discriminator
+                .as_ref()
+                .zip(variant.reference.as_deref())
+                .map(|(discriminator, reference)| self.tag_of(discriminator, reference));
+
+            self.resolve(variant, stack)?;
+
+            if let (Some(discriminator), Some(tag)) = (&schema.discriminator, tag) {
+                let tag_property = variant
+                    .properties
+                    .get_or_insert_with(Default::default)
+                    .entry(discriminator.property_name.clone())
+                    .or_insert_with(|| JsonSchema {
+                        ty: JsonSchemaTypes::String,
+                        ..Default::default()
+                    });
+
+                tag_property.const_value = Some(JsonSchemaValues::Str(tag));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn reference_name<'r>(&self, reference: &'r str) -> Result<&'r str, String> {
+        let Some(name) = self
+            .prefixes
+            .iter()
+            .find_map(|prefix| reference.strip_prefix(prefix))
+        else {
+            return Err(format!(
+                "`{}` can't be resolved, only `{}...` references are supported",
+                reference,
+                self.prefixes.join("...` and `")
+            ));
+        };
+
+        if !self.schemas.contains_key(name) {
+            return Err(format!("`{}` isn't in {}", reference, self.place));
+        }
+
+        Ok(name)
+    }
+
+    // the tag of a referenced variant, from the mapping, or the schema's name if it's not mapped
+    fn tag_of(&self, discriminator: &Discriminator, reference: &str) -> String {
+        let name = self
+            .prefixes
+            .iter()
+            .find_map(|prefix| reference.strip_prefix(prefix))
+            .unwrap_or(reference);
+
+        discriminator
+            .mapping
+            .iter()
+            .find(|(_, target)| *target == reference || *target == name)
+            .map_or_else(|| name.to_owned(), |(tag, _)| tag.clone())
+    }
+}
+
+// merges the properties of every member of an `allOf` into the schema, only objects can be merged
+//
+// a property that's required in any of them is required, and the first schema declaring a property
+// is the one it keeps
+fn merge_all_of(schema: &mut JsonSchema, members: Vec<JsonSchema>) -> Result<(), String> {
+    let mut required = schema.required.take().unwrap_or_default();
+    let mut properties = schema.properties.take().unwrap_or_default();
+
+    for (index, member) in members.into_iter().enumerate() {
+        let is_object = matches!(member.ty, JsonSchemaTypes::Object | JsonSchemaTypes::None)
+            && member.one_of.is_none()
+            && !member.is_map();
+
+        if !is_object {
+            return Err(format!(
+                "`allOf[{}]` isn't an object, only objects can be merged",
+                index
+            ));
+        }
+
+        for key in member.required.into_iter().flatten() {
+            if !required.contains(&key) {
+                required.push(key);
+            }
+        }
+
+        for (key, property) in member.properties.into_iter().flatten() {
+            properties.entry(key).or_insert(property);
+        }
+
+        schema.description = schema.description.take().or(member.description);
+    }
+
+    schema.ty = JsonSchemaTypes::Object;
+    schema.properties = Some(properties);
+    schema.required = Some(required);
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    /// writes a file into a directory of it's own for the current test process
+    pub(crate) fn temp_file(name: &str, content: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("schema2struct-core-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+
+        path
+    }
+
+    fn invalid(error: FileError) -> (String, String) {
+        match error.kind {
+            FileErrorKind::Invalid { location, message } => (location, message),
+            kind => panic!("expected an invalid schema, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn unsupported_keywords_are_dropped() {
+        let schema = parse_schema(json!({
+            "type": "object",
+            "properties": {
+                "id": { "type": "integer", "format": "int64", "minimum": 1 },
+                "name": { "type": "string", "maxLength": -1, "examples": ["a"] },
+                "email": { "type": "string", "format": "email" }
+            }
+        }))
+        .unwrap();
+
+        let properties = schema.properties.unwrap();
+
+        assert!(properties["id"].format.is_none());
+        assert_eq!(properties["id"].minimum, Some(1.into()));
+        assert!(properties["name"].max_lenght.is_none());
+        assert!(matches!(properties["email"].format, Some(Formats::Email)));
+    }
+
+    #[test]
+    fn type_lists_become_nullable() {
+        let schema = parse_schema(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": ["string", "null"] },
+                "id": { "type": ["string", "integer"] }
+            }
+        }))
+        .unwrap();
+
+        let properties = schema.properties.unwrap();
+
+        assert!(matches!(properties["name"].ty, JsonSchemaTypes::String));
+        assert_eq!(properties["name"].nullable, Some(true));
+        assert!(matches!(properties["id"].ty, JsonSchemaTypes::None));
+    }
+
+    #[test]
+    fn defs_are_resolved() {
+        let path = temp_file(
+            "order.json",
+            r##"{
+                "type": "object",
+                "properties": {
+                    "billing": { "$ref": "#/$defs/address" },
+                    "shipping": { "$ref": "#/definitions/location", "description": "where it goes" }
+                },
+                "$defs": {
+                    "address": { "type": "object", "properties": { "zip": { "type": "string" } } }
+                },
+                "definitions": {
+                    "location": { "$ref": "#/$defs/address" }
+                }
+            }"##,
+        );
+
+        let schema = read_schema_file(&path).unwrap();
+        let properties = schema.properties.unwrap();
+
+        assert_eq!(schema.struct_name.as_deref(), Some("Order"));
+        assert_eq!(
+            properties["billing"].struct_name.as_deref(),
+            Some("address")
+        );
+        assert!(properties["billing"]
+            .properties
+            .as_ref()
+            .unwrap()
+            .contains_key("zip"));
+        assert_eq!(
+            properties["shipping"].struct_name.as_deref(),
+            Some("location")
+        );
+        assert_eq!(
+            properties["shipping"].description.as_deref(),
+            Some("where it goes")
+        );
+    }
+
+    #[test]
+    fn unknown_and_recursive_defs_are_errors() {
+        let path = temp_file(
+            "missing.json",
+            r##"{ "type": "object", "properties": { "a": { "$ref": "#/$defs/b" } } }"##,
+        );
+
+        assert_eq!(
+            invalid(read_schema_file(&path).unwrap_err()),
+            (
+                "root".to_owned(),
+                "`#/$defs/b` isn't in the `$defs`".to_owned()
+            )
+        );
+
+        let path = temp_file(
+            "node.json",
+            r##"{
+                "type": "object",
+                "properties": { "next": { "$ref": "#/$defs/node" } },
+                "$defs": {
+                    "node": { "type": "object", "properties": { "next": { "$ref": "#/$defs/node" } } }
+                }
+            }"##,
+        );
+
+        assert_eq!(
+            invalid(read_schema_file(&path).unwrap_err()).1,
+            "`node` references itself through `node`, recursive types aren't supported"
+        );
+    }
+
+    #[test]
+    fn all_of_is_merged() {
+        let path = temp_file(
+            "admin.json",
+            r##"{
+                "allOf": [
+                    { "$ref": "#/$defs/user" },
+                    {
+                        "type": "object",
+                        "properties": { "role": { "type": "string" }, "name": { "type": "integer" } },
+                        "required": ["role"]
+                    }
+                ],
+                "$defs": {
+                    "user": {
+                        "type": "object",
+                        "description": "a user",
+                        "properties": { "name": { "type": "string" }, "email": { "type": "string" } },
+                        "required": ["name"]
+                    }
+                }
+            }"##,
+        );
+
+        let schema = read_schema_file(&path).unwrap();
+        let properties = schema.properties.as_ref().unwrap();
+
+        assert_eq!(
+            properties.keys().collect::<Vec<_>>(),
+            ["name", "email", "role"]
+        );
+        assert!(matches!(properties["name"].ty, JsonSchemaTypes::String));
+        assert_eq!(
+            schema.required,
+            Some(vec!["name".to_owned(), "role".to_owned()])
+        );
+        assert_eq!(schema.description.as_deref(), Some("a user"));
+
+        let path = temp_file("tags.json", r#"{ "allOf": [{ "type": "array" }] }"#);
+
+        assert_eq!(
+            invalid(read_schema_file(&path).unwrap_err()).1,
+            "`allOf[0]` isn't an object, only objects can be merged"
+        );
+    }
+
+    #[test]
+    fn file_input_takes_the_generation_options() {
+        let input = syn::parse_str::<FileInput>(
+            r#"file = "user.yaml", derive = [PartialEq], attrs = [#[non_exhaustive]], vis = pub(crate), mod = user, rename_all = camelCase"#,
+        )
+        .unwrap();
+
+        assert_eq!(input.path.value(), "user.yaml");
+        assert_eq!(input.options.derives.len(), 1);
+        assert_eq!(input.options.attrs.len(), 1);
+        assert!(matches!(
+            input.options.vis,
+            Some(syn::Visibility::Restricted(_))
+        ));
+        assert_eq!(input.options.module.unwrap(), "user");
+        assert!(matches!(input.options.rename_all, Some(RenameAll::Camel)));
+    }
+
+    #[test]
+    fn file_input_reports_an_unknown_key() {
+        let error = |input| match syn::parse_str::<FileInput>(input) {
+            Ok(_) => panic!("expected `{}` to be invalid", input),
+            Err(error) => error.to_string(),
+        };
+
+        assert_eq!(
+            error(r#"file = "user.yaml", derives = [PartialEq]"#),
+            "unknown key `derives`, did you mean `derive`?"
+        );
+        assert_eq!(
+            error(r#"file = "user.yaml", version = "v1""#),
+            "unknown key `version`, expected one of `file`, `derive`, `attrs`, `vis`, `mod`, `rename_all`"
+        );
+        assert_eq!(
+            error("derive = [PartialEq]"),
+            "unexpected end of input, `file = \"...\"` is required"
+        );
+    }
+
+    #[test]
+    fn the_root_must_be_an_object() {
+        let path = temp_file("names.yaml", "type: array\nitems:\n  type: string\n");
+
+        assert_eq!(
+            invalid(read_schema_file(&path).unwrap_err()),
+            (
+                "root".to_owned(),
+                "the root schema must be an object".to_owned()
+            )
+        );
+    }
+}
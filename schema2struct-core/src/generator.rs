@@ -0,0 +1,1272 @@
+use std::collections::{HashMap, HashSet};
+
+use quote::{format_ident, quote, ToTokens};
+use serde_json::{Map, Value};
+use syn::{ext::IdentExt as _, Ident};
+
+use crate::{
+    diagnostics,
+    models::{JsonSchema, JsonSchemaTypes, JsonSchemaValues, RenameAll},
+    naming::{field_ident, type_ident, SanitizedIdent, TypeNames},
+};
+
+/// the derives every generated struct gets, whatever the options are
+const DEFAULT_DERIVES: [&str; 5] = ["Deserialize", "Serialize", "Clone", "Debug", "Default"];
+
+/// the derives every generated const type gets, whatever the options are
+const CONST_DERIVES: [&str; 7] = [
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "PartialEq",
+    "Eq",
+    "Hash",
+];
+
+/// macro-level options, they apply to every generated type
+#[derive(Clone)]
+pub struct Options {
+    /// extra derives on top of the default ones
+    pub derives: Vec<syn::Path>,
+
+    /// extra attributes on every generated type
+    pub attrs: Vec<syn::Attribute>,
+
+    /// the visibility of every generated item, `pub` by default
+    pub vis: syn::Visibility,
+
+    /// wraps the output in a module, and only the root type gets re-exported
+    pub module: Option<Ident>,
+
+    /// normalizes the keys, otherwise every field keeps it's exact schema key
+    pub rename_all: Option<RenameAll>,
+
+    /// replaces the default Rust type of a schema type (`number`) or format (`date-time`),
+    /// the format is looked up first
+    pub type_mappings: HashMap<String, syn::Type>,
+
+    /// implements `schemars::JsonSchema` with the originating schema, it needs the `schemars`
+    /// feature of `schema2struct`
+    pub schemars: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            derives: Vec::new(),
+            attrs: Vec::new(),
+            vis: syn::parse_quote!(pub),
+            module: None,
+            rename_all: None,
+            type_mappings: HashMap::new(),
+            schemars: false,
+        }
+    }
+}
+
+impl From<&JsonSchema> for Options {
+    fn from(schema: &JsonSchema) -> Self {
+        let default = Self::default();
+
+        Self {
+            derives: schema.derives.clone().unwrap_or(default.derives),
+            attrs: schema.attrs.clone().unwrap_or(default.attrs),
+            vis: schema.vis.clone().unwrap_or(default.vis),
+            module: schema.module.clone(),
+            rename_all: schema.rename_all,
+            type_mappings: default.type_mappings,
+            schemars: default.schemars,
+        }
+    }
+}
+
+/// Generates the code for a whole schema, the root struct along with every nested type
+///
+/// # Parameters
+/// - `schema`: The root schema, it must have a `struct` name
+/// - `options`: The generation options, [`Options::from`] reads them from the schema itself
+///
+/// # Returns
+/// The generated code, or every error found while generating it
+///
+/// # Example
+/// ```ignore
+/// let schema = schema2struct_core::parse(tokens)?;
+/// let code = schema2struct_core::generate(&schema, &Options::from(&schema))?;
+/// ```
+pub fn generate(schema: &JsonSchema, options: &Options) -> syn::Result<proc_macro2::TokenStream> {
+    let Some(struct_name) = &schema.struct_name else {
+        return Err(syn::Error::new(
+            proc_macro2::Span::call_site(),
+            "the root schema needs a `struct` name",
+        ));
+    };
+
+    diagnostics::collect(|| {
+        let title = format_ident!("{}", struct_name);
+
+        let mut output = proc_macro2::TokenStream::new();
+
+        // the root name is taken before any nested struct gets to it
+        let mut type_names = TypeNames::default();
+        type_names.reserve(&title, &title.to_string());
+
+        let (main_struct, other_nested_struct) =
+            generate_root(schema, &title, options, &mut type_names);
+
+        output.extend(get_serde_const(&title, &options.vis));
+
+        output.extend(quote! {
+            #main_struct
+            #(#other_nested_struct)*
+        });
+
+        if let Some(module) = &options.module {
+            return Ok(wrap_in_module(output, module, &[title], &options.vis));
+        }
+
+        Ok(output)
+    })
+}
+
+/// Generates the types of several root schemas at once, like the components of an OpenAPI document
+///
+/// a nested type they share, like a referenced component, is only generated once, and a root that's
+/// nested in another root is the same type, with a `module` every root is re-exported
+///
+/// # Errors
+/// - A root schema has no `struct` name
+/// - Two different types end up with the same name
+pub fn generate_all(
+    schemas: &[JsonSchema],
+    options: &Options,
+) -> syn::Result<proc_macro2::TokenStream> {
+    generate_all_indexed(schemas, options).map_err(|(_, error)| error)
+}
+
+/// Same as [`generate_all`], but the error comes with the index of the first schema that has one,
+/// like the file a build script reports
+///
+/// a type that clashes with one from an earlier schema is reported on the later one
+///
+/// # Errors
+/// Every error of every schema, along with the index of the first one that has any
+pub fn generate_all_indexed(
+    schemas: &[JsonSchema],
+    options: &Options,
+) -> Result<proc_macro2::TokenStream, (usize, syn::Error)> {
+    let mut output = proc_macro2::TokenStream::new();
+    let mut type_names = TypeNames::default();
+    let mut roots = HashSet::new();
+    let mut titles = Vec::new();
+    let mut failed: Option<(usize, syn::Error)> = None;
+
+    for (index, schema) in schemas.iter().enumerate() {
+        // the errors of every schema are collected on their own, so it's known where they're from
+        let generated = diagnostics::collect(|| {
+            let Some(struct_name) = &schema.struct_name else {
+                return Err(syn::Error::new(
+                    proc_macro2::Span::call_site(),
+                    "every root schema needs a `struct` name",
+                ));
+            };
+
+            let title = format_ident!("{}", struct_name);
+
+            let (main_struct, other_nested_struct) =
+                generate_root(schema, &title, options, &mut type_names);
+
+            let mut output = proc_macro2::TokenStream::new();
+
+            let path = title.to_string();
+            let span = proc_macro2::Span::call_site();
+
+            // the same root can be asked for twice, like a component that's also a request body,
+            // it's still registered, so a different schema under the same name is reported
+            if roots.insert(path.clone()) {
+                output.extend(get_serde_const(&title, &options.vis));
+                titles.push(title.clone());
+            }
+
+            if type_names.register(&title, &path, &main_struct, span) {
+                output.extend(main_struct);
+            }
+
+            output.extend(other_nested_struct);
+
+            Ok(output)
+        });
+
+        match (generated, &mut failed) {
+            (Ok(generated), _) => output.extend(generated),
+            (Err(error), Some((_, errors))) => errors.combine(error),
+            (Err(error), None) => failed = Some((index, error)),
+        }
+    }
+
+    if let Some(failed) = failed {
+        return Err(failed);
+    }
+
+    if let Some(module) = &options.module {
+        return Ok(wrap_in_module(output, module, &titles, &options.vis));
+    }
+
+    Ok(output)
+}
+
+// generates the root type of a schema, along with every nested one
+fn generate_root(
+    schema: &JsonSchema,
+    title: &Ident,
+    options: &Options,
+    type_names: &mut TypeNames,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let json_struct = &JsonMacroInput {
+        struct_name: title.clone(),
+        content: schema.to_json_sample(),
+        path: title.to_string(),
+        schema,
+        options,
+    };
+
+    generate_type(json_struct, title, type_names)
+}
+
+// gets the whole schema as json and save it to a const value
+//
+// it's the same document as the root type's `HasJsonSchema`, so the string is only embedded once
+pub(crate) fn get_serde_const(
+    title: &syn::Ident,
+    vis: &syn::Visibility,
+) -> proc_macro2::TokenStream {
+    // Generate a constant name based on struct name
+    let const_json_ident = format_ident!("{}_{}", title.to_string().to_uppercase(), "JSON_VALUE");
+
+    quote! {
+        #vis static #const_json_ident: ::std::sync::LazyLock<::serde_json::Value> =
+            ::std::sync::LazyLock::new(||
+                ::serde_json::from_str(<#title as ::schema2struct::HasJsonSchema>::SCHEMA_STR)
+                    .expect("Couldn't convert the text into valid json")
+            );
+    }
+}
+
+// puts the generated items in their own module, so the nested structs don't fill the caller's scope
+//
+// only the root type gets re-exported, everything else is reachable through the module
+fn wrap_in_module(
+    output: proc_macro2::TokenStream,
+    module: &syn::Ident,
+    titles: &[syn::Ident],
+    vis: &syn::Visibility,
+) -> proc_macro2::TokenStream {
+    quote! {
+        #vis mod #module {
+            // the derives and attributes are written from the caller's scope
+            #[allow(unused_imports)]
+            use super::*;
+
+            #output
+        }
+
+        #(#vis use #module::#titles;)*
+    }
+}
+
+pub struct JsonMacroInput<'a> {
+    pub struct_name: Ident,
+    pub content: Value,
+    /// the property path of the struct, like `User.address`
+    pub path: String,
+    pub schema: &'a JsonSchema,
+    pub options: &'a Options,
+}
+/// Generates Rust structs from a JSON-like structure with flexible configuration.
+///
+/// # Parameters
+/// - `json_struct`: The input JSON macro structure
+/// - `base_name`: The base name for the primary struct
+/// - `type_names`: Every type name generated so far, shared with the nested structs
+///
+/// # Returns
+/// A tuple containing:
+/// 1. The main generated struct as a token stream
+/// 2. A vector of additional nested structs
+pub fn generate_structs(
+    json_struct: &JsonMacroInput,
+    base_name: &Ident,
+    type_names: &mut TypeNames,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    // Collect all generated structs
+    let mut all_structs = Vec::new();
+    let mut fields = Vec::new();
+
+    // the field names that were taken, along with the key they came from
+    let mut field_names: HashMap<String, &String> = HashMap::new();
+
+    let content = match json_struct.content.as_object() {
+        Some(obj) => obj,
+        None => &Map::new(),
+    };
+
+    for (key, value) in content {
+        if key.eq("struct_name") {
+            continue;
+        }
+
+        // the schema of the property, used for what the json sample can't hold
+        let property_schema = json_struct
+            .schema
+            .properties
+            .as_ref()
+            .and_then(|properties| properties.get(key));
+
+        let wire_name = key;
+
+        // Just in case the key is not a valid identifier, like `"type"` or `"$id"`
+        let SanitizedIdent {
+            ident: field_name,
+            changed,
+        } = field_ident(key);
+
+        let property_path = format!("{}.{}", json_struct.path, key);
+        let key_span = property_schema
+            .and_then(|s| s.current_key_span)
+            .unwrap_or_else(proc_macro2::Span::call_site);
+
+        if let Some(taken_by) = field_names.insert(field_name.unraw().to_string(), key) {
+            emit_error!(
+                key_span,
+                "`{}` and `{}` both become the field `{}`",
+                taken_by,
+                key,
+                field_name
+            );
+        }
+
+        // Infer field type and handle nested structures
+        let field_type =
+            match value {
+                // a `const` property only accepts one value, so it gets it's own unit type
+                //
+                // `Example`
+                //
+                //```rust
+                //
+                // struct User {
+                //  version: UserVersion
+                // }
+                //
+                // struct UserVersion; // (de)serializes only from/to the const value
+                //
+                //````
+                _ if property_schema.is_some_and(|s| s.const_value.is_some()) => {
+                    let const_schema = property_schema.expect("We already checked it's there");
+
+                    let const_name = type_ident(Some(base_name), key);
+
+                    let const_type =
+                        generate_const_type(&const_name, const_schema, json_struct.options);
+
+                    if type_names.register(&const_name, &property_path, &const_type, key_span) {
+                        all_structs.push(const_type);
+                    }
+
+                    const_name.into_token_stream()
+                }
+
+                Value::String(_) => {
+                    mapped_type(json_struct.options, property_schema).unwrap_or(quote!(String))
+                }
+                Value::Number(_) => mapped_type(json_struct.options, property_schema)
+                    .unwrap_or_else(|| match property_schema.map(|s| &s.ty) {
+                        Some(JsonSchemaTypes::Integer) => quote!(i64),
+                        _ => quote!(f64),
+                    }),
+                Value::Bool(_) => quote!(bool),
+
+                Value::Array(arr) => {
+                    let items_schema = property_schema.and_then(|s| s.items.as_deref());
+
+                    let elem_type =
+                        match (mapped_type(json_struct.options, items_schema), items_schema) {
+                            (Some(mapped_type), _) => mapped_type,
+
+                            // an array of objects gets a struct for it's items, named after the property
+                            (None, Some(items))
+                                if matches!(items.ty, JsonSchemaTypes::Object)
+                                    || items.one_of.is_some() =>
+                            {
+                                let (nested_name, nested_structs) = generate_nested_struct(
+                                    json_struct,
+                                    base_name,
+                                    key,
+                                    items,
+                                    &format!("{}[]", property_path),
+                                    key_span,
+                                    type_names,
+                                );
+
+                                all_structs.extend(nested_structs);
+
+                                nested_name.into_token_stream()
+                            }
+                            (None, Some(items)) if items.is_map() => {
+                                let (map_type, nested_structs) = value_type(
+                                    json_struct,
+                                    base_name,
+                                    key,
+                                    items,
+                                    &format!("{}[]", property_path),
+                                    key_span,
+                                    type_names,
+                                );
+
+                                all_structs.extend(nested_structs);
+
+                                map_type
+                            }
+                            (None, _) => infer_array_type(arr, items_schema).0,
+                        };
+
+                    match items_schema.and_then(|s| s.nullable) {
+                        Some(true) => quote!(Vec<Option<#elem_type>>),
+                        _ => quote!(Vec<#elem_type>),
+                    }
+                }
+
+                // an object with only `additionalProperties` is a map of them
+                //
+                // `Example`
+                //
+                //```rust
+                //
+                // struct User {
+                //  scores: BTreeMap<String, i64>
+                // }
+                //
+                //````
+                Value::Object(_) if property_schema.is_some_and(JsonSchema::is_map) => {
+                    let (map_type, nested_structs) = value_type(
+                        json_struct,
+                        base_name,
+                        key,
+                        property_schema.expect("We already checked it's there"),
+                        &property_path,
+                        key_span,
+                        type_names,
+                    );
+
+                    all_structs.extend(nested_structs);
+
+                    map_type
+                }
+
+                Value::Object(_) => {
+                    // Generate nested struct for object and concat the key with the struct name
+                    //
+                    // `Example`
+                    //
+                    //```rust
+                    //
+                    // struct User {
+                    //  age: UserAge
+                    // }
+                    //
+                    // struct UserAge;
+                    //
+                    //````
+                    let (nested_name, nested_structs) = generate_nested_struct(
+                        json_struct,
+                        base_name,
+                        key,
+                        property_schema.expect("every object sample comes from a property"),
+                        &property_path,
+                        key_span,
+                        type_names,
+                    );
+
+                    all_structs.extend(nested_structs);
+
+                    nested_name.into_token_stream()
+                }
+                Value::Null => quote!(Option<::serde_json::Value>),
+            };
+
+        // a property that's left out of the `required`, or that can be `null`, becomes an `Option`
+        //
+        // an optional one can be missing, and it's skipped when it's `None`
+        let optional = json_struct
+            .schema
+            .required
+            .as_ref()
+            .is_some_and(|required| !required.contains(key));
+        let nullable = property_schema.and_then(|s| s.nullable).unwrap_or(false);
+
+        let field_type = if (optional || nullable) && !value.is_null() {
+            quote!(Option<#field_type>)
+        } else {
+            field_type
+        };
+
+        let optional_attr =
+            optional.then(|| quote!(#[serde(default, skip_serializing_if = "Option::is_none")]));
+
+        // Handle the Serde key configuration
+        //
+        // by default every field is (de)serialized with the exact key written in the schema,
+        // so a `"user_ID"` property is still `"user_ID"` on the wire, not `userId`
+        //
+        // ```rust
+        // struct User {
+        //   #[serde(rename = "user_ID")]
+        //   user_id: String
+        // }
+        // ```
+        //
+        // with `rename_all` the keys get normalized instead, and the `#[serde(alias = "user_ID")]`
+        // allows you to still deserialize with the key as written in the schema,
+        // unless the key had to change to be a valid identifier, then there's nothing to normalize
+        let serde_key = match json_struct.options.rename_all {
+            Some(_) if !changed => quote!(#[serde(alias = #wire_name)]),
+            _ => quote!(#[serde(rename = #wire_name)]),
+        };
+
+        let field_attrs = property_schema
+            .and_then(|s| s.attrs.as_deref())
+            .unwrap_or_default();
+
+        let vis = &json_struct.options.vis;
+
+        let field = quote! {
+            #(#field_attrs)*
+            #serde_key
+            #optional_attr
+            #vis #field_name: #field_type
+        };
+
+        fields.push(field);
+    }
+
+    let extra_derives = extra_derives(json_struct.options, json_struct.schema);
+
+    let attrs = &json_struct.options.attrs;
+    let vis = &json_struct.options.vis;
+
+    let rename_all = json_struct
+        .options
+        .rename_all
+        .map(|rename_all| rename_all.to_string())
+        .map(|rename_all| quote!(#[serde(rename_all = #rename_all)]));
+
+    let has_json_schema =
+        generate_has_json_schema(base_name, json_struct.schema, json_struct.options);
+
+    // Generate the main struct with optional rename strategy
+    let main_struct = quote! {
+        #[derive(::serde::Deserialize, ::serde::Serialize, ::std::clone::Clone, ::std::fmt::Debug, ::std::default::Default, #(#extra_derives),*)]
+        #(#attrs)*
+        #rename_all
+        #vis struct #base_name {
+            #(#fields),*
+        }
+
+        #has_json_schema
+    };
+
+    (main_struct, all_structs)
+}
+
+// generates the type of an object schema, it's a struct, or an enum when it's a `oneOf`
+fn generate_type(
+    json_struct: &JsonMacroInput,
+    name: &Ident,
+    type_names: &mut TypeNames,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    match json_struct.schema.one_of.as_deref() {
+        Some(variants) => generate_one_of_type(json_struct, name, variants, type_names),
+        None => generate_structs(json_struct, name, type_names),
+    }
+}
+
+/// Generates the enum of a `oneOf` schema, with a variant for every schema it can be
+///
+/// with a `discriminator` the enum is tagged by it's property, every variant struct is generated
+/// without it, like `PetCat` for the `Cat` variant of `Pet`, and gets tagged by the property's
+/// `const`, or by the variant's own name, otherwise it's untagged and the first variant that fits
+/// is taken
+///
+/// # Parameters
+/// - `json_struct`: The `oneOf` schema
+/// - `name`: The name of the enum
+/// - `variants`: The schemas of the `oneOf`
+/// - `type_names`: Every type name generated so far
+///
+/// # Returns
+/// The enum, and every type generated for it's variants
+fn generate_one_of_type(
+    json_struct: &JsonMacroInput,
+    name: &Ident,
+    variants: &[JsonSchema],
+    type_names: &mut TypeNames,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let schema = json_struct.schema;
+    let options = json_struct.options;
+    let span = schema
+        .current_key_span
+        .unwrap_or_else(proc_macro2::Span::call_site);
+
+    let tag = schema
+        .discriminator
+        .as_ref()
+        .map(|discriminator| discriminator.property_name.as_str());
+
+    let mut all_types = Vec::new();
+    let mut variant_names = HashSet::new();
+    let mut enum_variants = Vec::new();
+    let mut first_variant = None;
+
+    if variants.is_empty() {
+        emit_error!(
+            span,
+            "`{}` needs at least one schema in it's `oneOf`",
+            json_struct.path
+        );
+    }
+
+    for (index, variant) in variants.iter().enumerate() {
+        let variant_path = format!("{}.oneOf[{}]", json_struct.path, index);
+
+        // named after the variant's schema, or after it's type
+        let mut variant_name = match (
+            variant.struct_name.as_deref().or(variant.title.as_deref()),
+            &variant.ty,
+        ) {
+            (Some(variant_name), _) => type_ident(None, variant_name),
+            (None, JsonSchemaTypes::None) => format_ident!("Variant{}", index),
+            (None, ty) => type_ident(None, &ty.to_string()),
+        };
+
+        if !variant_names.insert(variant_name.to_string()) {
+            variant_name = format_ident!("{}{}", variant_name, index);
+        }
+
+        let (variant_schema, rename) = match tag {
+            Some(tag) => match tagged_variant(variant, tag) {
+                Some((variant, tag_value)) => (
+                    std::borrow::Cow::Owned(variant),
+                    Some(quote!(#[serde(rename = #tag_value)])),
+                ),
+                None => {
+                    emit_error!(
+                        span,
+                        "`{}` has to be an object with a `const` `{}`, or a referenced schema, to be tagged",
+                        variant_path,
+                        tag
+                    );
+                    continue;
+                }
+            },
+            None => (std::borrow::Cow::Borrowed(variant), None),
+        };
+
+        let variant_type = match (
+            mapped_type(options, Some(&variant_schema)),
+            &variant_schema.ty,
+        ) {
+            (Some(mapped_type), _) => mapped_type,
+            (None, JsonSchemaTypes::Object) => {
+                let (nested_name, nested_types) = generate_nested_struct(
+                    json_struct,
+                    name,
+                    &variant_name.to_string(),
+                    &variant_schema,
+                    &variant_path,
+                    span,
+                    type_names,
+                );
+
+                all_types.extend(nested_types);
+
+                nested_name.into_token_stream()
+            }
+            (None, JsonSchemaTypes::String) => quote!(String),
+            (None, JsonSchemaTypes::Integer) => quote!(i64),
+            (None, JsonSchemaTypes::Number) => quote!(f64),
+            (None, JsonSchemaTypes::Boolean) => quote!(bool),
+            (None, JsonSchemaTypes::Array) => quote!(Vec<::serde_json::Value>),
+            (None, JsonSchemaTypes::None) => quote!(::serde_json::Value),
+        };
+
+        enum_variants.push(quote! {
+            #rename
+            #variant_name(#variant_type)
+        });
+
+        first_variant.get_or_insert(variant_name);
+    }
+
+    let serde_tag = match tag {
+        Some(tag) => quote!(#[serde(tag = #tag)]),
+        None => quote!(#[serde(untagged)]),
+    };
+
+    // an enum can't derive `Default`, it defaults to it's first variant instead
+    let default_impl = first_variant.map(|first_variant| {
+        quote! {
+            impl ::std::default::Default for #name {
+                fn default() -> Self {
+                    Self::#first_variant(::std::default::Default::default())
+                }
+            }
+        }
+    });
+
+    let extra_derives = extra_derives(options, schema);
+
+    let attrs = &options.attrs;
+    let vis = &options.vis;
+
+    let has_json_schema = generate_has_json_schema(name, schema, options);
+
+    let main_enum = quote! {
+        #[derive(::serde::Deserialize, ::serde::Serialize, ::std::clone::Clone, ::std::fmt::Debug, #(#extra_derives),*)]
+        #(#attrs)*
+        #serde_tag
+        #vis enum #name {
+            #(#enum_variants),*
+        }
+
+        #default_impl
+
+        #has_json_schema
+    };
+
+    (main_enum, all_types)
+}
+
+/// Removes the tag property from a variant of a tagged `oneOf`, serde reads the tag before the
+/// variant does, so the variant's struct is named after the enum
+///
+/// # Returns
+/// The variant without the tag, along with the tag's value, or `None` if it's not an object or
+/// there's no way to tell the tag
+fn tagged_variant(variant: &JsonSchema, tag: &str) -> Option<(JsonSchema, String)> {
+    if !matches!(variant.ty, JsonSchemaTypes::Object) {
+        return None;
+    }
+
+    let mut variant = variant.clone();
+
+    let property = variant
+        .properties
+        .as_mut()
+        .and_then(|properties| properties.shift_remove(tag));
+
+    if let Some(required) = &mut variant.required {
+        required.retain(|key| key != tag);
+    }
+
+    let tag_value = match property.and_then(|property| property.const_value) {
+        Some(JsonSchemaValues::Str(tag_value)) => tag_value,
+        _ => variant.struct_name.clone()?,
+    };
+
+    // it's not the same schema anymore, so it's named after the enum instead, like `PetCat`
+    variant.struct_name = None;
+
+    Some((variant, tag_value))
+}
+
+/// Generates the struct of an object property, or of the items of an array property
+///
+/// # Parameters
+/// - `json_struct`: The struct holding the property
+/// - `base_name`: The name of the struct holding the property
+/// - `key`: The key of the property
+/// - `schema`: The object schema of the nested struct
+/// - `property_path`: The path of the nested struct, for the conflict errors
+/// - `key_span`: Where the property is written
+/// - `type_names`: Every type name generated so far
+///
+/// # Returns
+/// The name of the nested struct, and every struct generated along with it
+fn generate_nested_struct(
+    json_struct: &JsonMacroInput,
+    base_name: &Ident,
+    key: &str,
+    schema: &JsonSchema,
+    property_path: &str,
+    key_span: proc_macro2::Span,
+    type_names: &mut TypeNames,
+) -> (Ident, Vec<proc_macro2::TokenStream>) {
+    let nested_name = match schema.struct_name.as_deref() {
+        Some("key") => type_ident(None, key),
+        Some(struct_name) => type_ident(None, struct_name),
+        None => type_ident(Some(base_name), key),
+    };
+
+    // being `null` is up to where it's used, the `Option` is there, so a type used both ways is
+    // still the same type
+    let schema = match schema.nullable {
+        Some(_) => std::borrow::Cow::Owned(JsonSchema {
+            nullable: None,
+            ..schema.clone()
+        }),
+        None => std::borrow::Cow::Borrowed(schema),
+    };
+
+    let nested_macro_input = JsonMacroInput {
+        struct_name: json_struct.struct_name.clone(),
+        content: schema.to_json_sample(),
+        path: property_path.to_owned(),
+        schema: &schema,
+        options: json_struct.options,
+    };
+
+    // Recursively generate nested structs
+    let (nested_struct, mut nested_structs) =
+        generate_type(&nested_macro_input, &nested_name, type_names);
+
+    // two properties can end up with the same type name, like two `struct: key` with the same key
+    if type_names.register(&nested_name, property_path, &nested_struct, key_span) {
+        nested_structs.push(nested_struct);
+    }
+
+    (nested_name, nested_structs)
+}
+
+/// Gets the type of a map, or of the values in it
+///
+/// # Parameters
+/// - `key`: The property holding it, the structs of it's objects are named after it
+/// - `schema`: The schema of the value, a map is a `BTreeMap` of it's `additionalProperties`
+/// - `property_path`: The path of the value, like `User.scores{}`
+///
+/// # Returns
+/// The type, and every struct generated along with it
+fn value_type(
+    json_struct: &JsonMacroInput,
+    base_name: &Ident,
+    key: &str,
+    schema: &JsonSchema,
+    property_path: &str,
+    key_span: proc_macro2::Span,
+    type_names: &mut TypeNames,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    let mut nested_structs = Vec::new();
+
+    let ty = match (mapped_type(json_struct.options, Some(schema)), &schema.ty) {
+        (Some(mapped_type), _) => mapped_type,
+        (None, JsonSchemaTypes::Object) if schema.is_map() => {
+            let values = schema
+                .additional_properties
+                .as_deref()
+                .expect("a map has the schema of it's values");
+
+            let (values_type, structs) = value_type(
+                json_struct,
+                base_name,
+                key,
+                values,
+                &format!("{}{{}}", property_path),
+                key_span,
+                type_names,
+            );
+            nested_structs.extend(structs);
+
+            let values_type = match values.nullable {
+                Some(true) => quote!(Option<#values_type>),
+                _ => values_type,
+            };
+
+            quote!(::std::collections::BTreeMap<String, #values_type>)
+        }
+        (None, ty) if matches!(ty, JsonSchemaTypes::Object) || schema.one_of.is_some() => {
+            let (nested_name, structs) = generate_nested_struct(
+                json_struct,
+                base_name,
+                key,
+                schema,
+                property_path,
+                key_span,
+                type_names,
+            );
+            nested_structs.extend(structs);
+
+            nested_name.into_token_stream()
+        }
+        (None, JsonSchemaTypes::Array) => {
+            let items_type = match schema.items.as_deref() {
+                Some(items) => {
+                    let (items_type, structs) = value_type(
+                        json_struct,
+                        base_name,
+                        key,
+                        items,
+                        &format!("{}[]", property_path),
+                        key_span,
+                        type_names,
+                    );
+                    nested_structs.extend(structs);
+
+                    match items.nullable {
+                        Some(true) => quote!(Option<#items_type>),
+                        _ => items_type,
+                    }
+                }
+                None => quote!(::serde_json::Value),
+            };
+
+            quote!(Vec<#items_type>)
+        }
+        (None, JsonSchemaTypes::String) => quote!(String),
+        (None, JsonSchemaTypes::Integer) => quote!(i64),
+        (None, JsonSchemaTypes::Number) => quote!(f64),
+        (None, JsonSchemaTypes::Boolean) => quote!(bool),
+        (None, JsonSchemaTypes::None | JsonSchemaTypes::Object) => quote!(::serde_json::Value),
+    };
+
+    (ty, nested_structs)
+}
+
+/// Generates a unit struct that stands for a single `const` value
+///
+/// # Parameters
+/// - `name`: The name of the generated type
+/// - `schema`: The schema of the property, it's `const` is the only value the type accepts
+///
+/// # Returns
+/// The unit struct along with it's serde implementations, it only deserializes from
+/// exactly the const value and always serializes to it
+fn generate_const_type(
+    name: &Ident,
+    schema: &JsonSchema,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let value = schema
+        .const_value
+        .as_ref()
+        .expect("const types are only generated for a `const` property");
+
+    let (value_type, value_literal) = match value {
+        JsonSchemaValues::Str(s) => (quote!(::std::string::String), quote!(#s)),
+        // an integer stays an `i64`, anything else is an `f64`
+        JsonSchemaValues::Number(num) => match num.as_i64() {
+            Some(int) => (quote!(i64), quote!(#int)),
+            None => {
+                let float = num.as_f64().unwrap_or_default();
+                (quote!(f64), quote!(#float))
+            }
+        },
+        JsonSchemaValues::Bool(b) => (quote!(bool), quote!(#b)),
+        JsonSchemaValues::Char(c) => (quote!(char), quote!(#c)),
+        // the macro checks it at parsing, but a schema file can still have one
+        JsonSchemaValues::Ident(_) | JsonSchemaValues::Array(_) => {
+            emit_error!(
+                schema
+                    .current_key_span
+                    .unwrap_or_else(proc_macro2::Span::call_site),
+                "`{}` has a `const` of `{}`, only a string, a number, a boolean or a char can be a const type",
+                name,
+                serde_json::to_string(value).unwrap_or_default()
+            );
+
+            return proc_macro2::TokenStream::new();
+        }
+    };
+
+    // a number is compared as a float, so `2.0` matches a `const` of `2`
+    let (compared_type, compared_literal) = match value {
+        JsonSchemaValues::Number(num) => {
+            let float = num.as_f64().unwrap_or_default();
+            (quote!(f64), quote!(#float))
+        }
+        _ => (value_type.clone(), value_literal.clone()),
+    };
+
+    let expected = format!("the const value `{}`", value);
+
+    // a unit struct can derive anything, and the struct holding it may need them
+    let extra_derives = options
+        .derives
+        .iter()
+        .filter(|derive| !CONST_DERIVES.contains(&derive_name(derive).as_str()));
+
+    let attrs = &options.attrs;
+    let vis = &options.vis;
+
+    let has_json_schema = generate_has_json_schema(name, schema, options);
+
+    quote! {
+        #[derive(::std::clone::Clone, ::std::marker::Copy, ::std::fmt::Debug, ::std::default::Default, ::std::cmp::PartialEq, ::std::cmp::Eq, ::std::hash::Hash, #(#extra_derives),*)]
+        #(#attrs)*
+        #vis struct #name;
+
+        impl #name {
+            /// The only value this type accepts
+            #vis fn value(&self) -> #value_type {
+                // `-1i64.into()` would negate what `into` returns
+                ::std::convert::Into::into(#value_literal)
+            }
+        }
+
+        impl ::serde::Serialize for #name {
+            fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                ::serde::Serialize::serialize(&#value_literal, serializer)
+            }
+        }
+
+        impl<'de> ::serde::Deserialize<'de> for #name {
+            fn deserialize<D>(deserializer: D) -> ::std::result::Result<Self, D::Error>
+            where
+                D: ::serde::Deserializer<'de>,
+            {
+                let value = <#compared_type as ::serde::Deserialize>::deserialize(deserializer)?;
+
+                if value == #compared_literal {
+                    ::std::result::Result::Ok(#name)
+                } else {
+                    ::std::result::Result::Err(<D::Error as ::serde::de::Error>::custom(
+                        ::std::format!("expected {}, found `{}`", #expected, value),
+                    ))
+                }
+            }
+        }
+
+        #has_json_schema
+    }
+}
+
+/// Implements `HasJsonSchema` for a generated type, and `schemars::JsonSchema` if it's enabled
+///
+/// # Parameters
+/// - `name`: The name of the generated type
+/// - `schema`: The schema the type was generated from
+/// - `options`: The generation options
+///
+/// # Returns
+/// The trait implementations, with the schema as a standalone document titled after the type,
+/// unless it has it's own `title`
+fn generate_has_json_schema(
+    name: &Ident,
+    schema: &JsonSchema,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let mut schema = schema.clone();
+    schema.struct_name = Some(name.to_string());
+
+    let schema_str = serde_json::to_string(&schema.to_json_schema()).unwrap_or_default();
+
+    impl_has_json_schema(name, &quote!(#schema_str), options)
+}
+
+/// Implements `HasJsonSchema` for a type from it's schema string, and `schemars::JsonSchema` if
+/// it's enabled
+///
+/// # Parameters
+/// - `name`: The name of the type
+/// - `schema_str`: A const expression of the schema as a JSON string
+/// - `options`: The generation options
+pub(crate) fn impl_has_json_schema(
+    name: &Ident,
+    schema_str: &proc_macro2::TokenStream,
+    options: &Options,
+) -> proc_macro2::TokenStream {
+    let schemars = options.schemars.then(|| generate_schemars_impl(name));
+
+    quote! {
+        impl ::schema2struct::HasJsonSchema for #name {
+            const SCHEMA_STR: &'static str = #schema_str;
+
+            fn json_schema() -> &'static ::serde_json::Value {
+                static SCHEMA: ::std::sync::LazyLock<::serde_json::Value> =
+                    ::std::sync::LazyLock::new(||
+                        ::serde_json::from_str(<#name as ::schema2struct::HasJsonSchema>::SCHEMA_STR)
+                            .expect("Couldn't convert the text into valid json")
+                    );
+
+                &SCHEMA
+            }
+        }
+
+        #schemars
+    }
+}
+
+/// Implements `schemars::JsonSchema` for a generated type, with the schema it came from instead of
+/// one derived from it's fields, so the constraints, descriptions and formats are kept
+fn generate_schemars_impl(name: &Ident) -> proc_macro2::TokenStream {
+    let schema_name = name.to_string();
+
+    quote! {
+        impl ::schema2struct::schemars::JsonSchema for #name {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                #schema_name.into()
+            }
+
+            // two macro calls can generate the same name in different modules
+            fn schema_id() -> ::std::borrow::Cow<'static, str> {
+                ::std::concat!(::std::module_path!(), "::", #schema_name).into()
+            }
+
+            fn json_schema(
+                _: &mut ::schema2struct::schemars::SchemaGenerator,
+            ) -> ::schema2struct::schemars::Schema {
+                let mut schema = ::schema2struct::schemars::Schema::try_from(
+                    <Self as ::schema2struct::HasJsonSchema>::json_schema().clone(),
+                )
+                .expect("a generated schema is always an object");
+
+                // the dialect belongs to the document the schema ends up in
+                schema.remove("$schema");
+                schema
+            }
+        }
+    }
+}
+
+/// The derives from the options that the type of a schema gets on top of the default ones
+///
+/// the derives the type can't implement are dropped, instead of failing to compile
+pub(crate) fn extra_derives<'a>(
+    options: &'a Options,
+    schema: &JsonSchema,
+) -> impl Iterator<Item = &'a syn::Path> {
+    let impossible_derives = impossible_derives(schema);
+
+    options.derives.iter().filter(move |derive| {
+        let name = derive_name(derive);
+
+        !DEFAULT_DERIVES.contains(&name.as_str()) && !impossible_derives.contains(name.as_str())
+    })
+}
+
+/// Gets the name of a derive without it's path
+///
+/// `::std::hash::Hash` becomes `Hash`
+fn derive_name(derive: &syn::Path) -> String {
+    derive
+        .segments
+        .last()
+        .map(|segment| segment.ident.to_string())
+        .unwrap_or_default()
+}
+
+/// Collects the standard derives that the generated type of a schema can't implement
+///
+/// # Parameters
+/// - `schema`: The schema of the type
+///
+/// # Returns
+/// The names of the derives to leave out, for example `f64` has no `Eq`, `Hash` nor `Ord`
+fn impossible_derives(schema: &JsonSchema) -> HashSet<&'static str> {
+    const F64: [&str; 3] = ["Eq", "Hash", "Ord"];
+    const STRING: [&str; 1] = ["Copy"];
+    const JSON_VALUE: [&str; 3] = ["PartialOrd", "Ord", "Copy"];
+
+    // const types derive everything
+    if schema.const_value.is_some() {
+        return HashSet::new();
+    }
+
+    // an enum can derive what all of it's variants can
+    if let Some(variants) = &schema.one_of {
+        return variants.iter().flat_map(impossible_derives).collect();
+    }
+
+    match schema.ty {
+        JsonSchemaTypes::Number => F64.into(),
+        JsonSchemaTypes::String => STRING.into(),
+        JsonSchemaTypes::Integer | JsonSchemaTypes::Boolean => HashSet::new(),
+        JsonSchemaTypes::None => JSON_VALUE.into(),
+        JsonSchemaTypes::Array => {
+            let elements = match schema.items.as_deref() {
+                Some(items)
+                    if matches!(items.ty, JsonSchemaTypes::Object) || items.one_of.is_some() =>
+                {
+                    impossible_derives(items)
+                }
+                Some(items) => match items.ty {
+                    JsonSchemaTypes::String
+                    | JsonSchemaTypes::Integer
+                    | JsonSchemaTypes::Boolean => HashSet::new(),
+                    JsonSchemaTypes::Number => F64.into(),
+                    _ => JSON_VALUE.into(),
+                },
+                None => JSON_VALUE.into(),
+            };
+
+            // a `Vec` is never `Copy`
+            elements.into_iter().chain(["Copy"]).collect()
+        }
+        // a `BTreeMap` derives everything but `Copy`
+        JsonSchemaTypes::Object if schema.is_map() => schema
+            .additional_properties
+            .as_deref()
+            .map(impossible_derives)
+            .unwrap_or_default()
+            .into_iter()
+            .chain(["Copy"])
+            .collect(),
+        JsonSchemaTypes::Object => schema
+            .properties
+            .iter()
+            .flat_map(|properties| properties.values())
+            .flat_map(impossible_derives)
+            .collect(),
+    }
+}
+
+/// Looks up the type mapping of a schema, by it's format first then by it's type
+///
+/// # Returns
+/// The mapped type, or `None` to keep the default one
+pub(crate) fn mapped_type(
+    options: &Options,
+    schema: Option<&JsonSchema>,
+) -> Option<proc_macro2::TokenStream> {
+    let schema = schema?;
+
+    schema
+        .format
+        .and_then(|format| options.type_mappings.get(&format.to_string()))
+        .or_else(|| options.type_mappings.get(&schema.ty.to_string()))
+        .map(ToTokens::into_token_stream)
+}
+
+/// Infers the element type for an array of JSON values.
+///
+/// # Parameters
+/// - `arr`: A slice of JSON values
+/// - `items_schema`: The schema of the items, it tells an integer apart from a float
+///
+/// # Returns
+/// A tuple containing:
+/// 1. The inferred element type as a token stream
+/// 2. Any additional generated structs (currently unused)
+fn infer_array_type(
+    arr: &[Value],
+    items_schema: Option<&JsonSchema>,
+) -> (proc_macro2::TokenStream, Vec<proc_macro2::TokenStream>) {
+    // Handle empty array
+    if arr.is_empty() {
+        return (quote!(::serde_json::Value), Vec::new());
+    }
+
+    // Infer type based on first element
+    match &arr[0] {
+        Value::String(_) => (quote!(String), Vec::new()),
+        Value::Number(_) => match items_schema.map(|s| &s.ty) {
+            Some(JsonSchemaTypes::Integer) => (quote!(i64), Vec::new()),
+            _ => (quote!(f64), Vec::new()),
+        },
+        Value::Bool(_) => (quote!(bool), Vec::new()),
+        _ => (quote!(::serde_json::Value), Vec::new()),
+    }
+}
@@ -0,0 +1,228 @@
+/// Schema inference module
+///
+/// Infers a [`JsonSchema`] from example JSON documents, it's the inverse of
+/// [`JsonSchema::to_json_sample`]
+///
+/// # Merging
+/// Every sample is inferred on it's own, then they're merged into one schema:
+/// - A property missing from a sample is left out of the `required`
+/// - A property that's `null` in a sample is `nullable`
+/// - An integer and a float become a `number`, only integers stay an `integer`
+/// - The items of an array are merged together, so an array of objects gets every key of them
+/// - Two different types can't be merged, they're left without a type, which is any JSON value
+use serde_json::Value;
+
+use crate::models::{JsonSchema, JsonSchemaTypes};
+
+/// Infers the schema of one or more example documents
+///
+/// # Arguments
+/// * `samples` - The example documents, every one of them must be an object
+///
+/// # Errors
+/// - There are no samples
+/// - A sample isn't an object
+pub fn infer_schema(samples: &[Value]) -> Result<JsonSchema, String> {
+    if samples.is_empty() {
+        return Err("at least one example is needed".to_owned());
+    }
+
+    if let Some(position) = samples.iter().position(|sample| !sample.is_object()) {
+        return Err(format!(
+            "example {} is not an object, only objects can become a struct",
+            position + 1
+        ));
+    }
+
+    let mut schema = samples
+        .iter()
+        .map(infer_value)
+        .reduce(merge)
+        .expect("We already checked there's a sample");
+
+    schema.depth = 1;
+
+    Ok(schema)
+}
+
+// infers the schema of a single value
+fn infer_value(value: &Value) -> JsonSchema {
+    let mut schema = JsonSchema::default();
+
+    match value {
+        Value::Null => schema.nullable = Some(true),
+        Value::Bool(_) => schema.ty = JsonSchemaTypes::Boolean,
+        Value::Number(number) if number.is_f64() => schema.ty = JsonSchemaTypes::Number,
+        Value::Number(_) => schema.ty = JsonSchemaTypes::Integer,
+        Value::String(_) => schema.ty = JsonSchemaTypes::String,
+        Value::Array(array) => {
+            schema.ty = JsonSchemaTypes::Array;
+            schema.items = array.iter().map(infer_value).reduce(merge).map(Box::new);
+        }
+        Value::Object(object) => {
+            schema.ty = JsonSchemaTypes::Object;
+            schema.required = Some(object.keys().cloned().collect());
+            schema.properties = Some(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.to_owned(), infer_value(value)))
+                    .collect(),
+            );
+        }
+    }
+
+    schema
+}
+
+// merges two inferred schemas of the same value
+fn merge(a: JsonSchema, b: JsonSchema) -> JsonSchema {
+    let nullable = (a.nullable == Some(true) || b.nullable == Some(true)).then_some(true);
+
+    let mut merged = match (&a.ty, &b.ty) {
+        // a `null` only says the value can be missing, the other sample has the type
+        (JsonSchemaTypes::None, _) if a.nullable == Some(true) => b,
+        (_, JsonSchemaTypes::None) if b.nullable == Some(true) => a,
+
+        (JsonSchemaTypes::Integer, JsonSchemaTypes::Number)
+        | (JsonSchemaTypes::Number, JsonSchemaTypes::Integer) => JsonSchema {
+            ty: JsonSchemaTypes::Number,
+            ..Default::default()
+        },
+
+        (JsonSchemaTypes::Object, JsonSchemaTypes::Object) => merge_objects(a, b),
+
+        (JsonSchemaTypes::Array, JsonSchemaTypes::Array) => JsonSchema {
+            ty: JsonSchemaTypes::Array,
+            items: match (a.items, b.items) {
+                (Some(a), Some(b)) => Some(Box::new(merge(*a, *b))),
+                (items, None) | (None, items) => items,
+            },
+            ..Default::default()
+        },
+
+        (a_ty, b_ty) if std::mem::discriminant(a_ty) == std::mem::discriminant(b_ty) => a,
+
+        // there's no type for both, so it's kept as any value
+        _ => JsonSchema::default(),
+    };
+
+    merged.nullable = nullable;
+    merged
+}
+
+// merges the properties of two objects, the keys keep the order they were first seen in
+fn merge_objects(a: JsonSchema, b: JsonSchema) -> JsonSchema {
+    let a_required = a.required.unwrap_or_default();
+    let b_required = b.required.unwrap_or_default();
+
+    let mut properties = a.properties.unwrap_or_default();
+
+    for (key, b_property) in b.properties.unwrap_or_default() {
+        match properties.get_mut(&key) {
+            Some(a_property) => *a_property = merge(std::mem::take(a_property), b_property),
+            None => {
+                properties.insert(key, b_property);
+            }
+        }
+    }
+
+    // only what's in every sample is required
+    let required = properties
+        .keys()
+        .filter(|key| a_required.contains(key) && b_required.contains(key))
+        .cloned()
+        .collect();
+
+    JsonSchema {
+        ty: JsonSchemaTypes::Object,
+        properties: Some(properties),
+        required: Some(required),
+        ..Default::default()
+    }
+}
+
+/// Parses the example documents, an example can be a single document or an array of them
+///
+/// # Errors
+/// An example isn't valid JSON, the position is 1-based
+pub fn parse_examples<'a>(
+    examples: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<Value>, serde_json::Error> {
+    let mut samples = Vec::new();
+
+    for example in examples {
+        match serde_json::from_str(example)? {
+            Value::Array(array) => samples.extend(array),
+            sample => samples.push(sample),
+        }
+    }
+
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn infer(samples: Value) -> Value {
+        let Value::Array(samples) = samples else {
+            unreachable!()
+        };
+
+        serde_json::to_value(infer_schema(&samples).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn integer_and_float_become_a_number() {
+        let schema = infer(json!([{ "price": 1 }, { "price": 1.5 }]));
+        assert_eq!(schema["properties"]["price"]["type"], "number");
+
+        let schema = infer(json!([{ "count": 1 }, { "count": 2 }]));
+        assert_eq!(schema["properties"]["count"]["type"], "integer");
+    }
+
+    #[test]
+    fn missing_property_is_optional() {
+        let schema = infer(json!([{ "id": 1, "name": "a" }, { "id": 2 }]));
+
+        assert_eq!(schema["required"], json!(["id"]));
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+    }
+
+    #[test]
+    fn null_property_is_nullable() {
+        let schema = infer(json!([{ "name": null }, { "name": "a" }]));
+
+        assert_eq!(schema["properties"]["name"]["type"], "string");
+        assert_eq!(schema["properties"]["name"]["nullable"], true);
+        assert_eq!(schema["required"], json!(["name"]));
+    }
+
+    #[test]
+    fn array_items_are_merged() {
+        let schema = infer(json!([{ "tags": [{ "a": 1 }, { "b": true }] }]));
+        let items = &schema["properties"]["tags"]["items"];
+
+        assert_eq!(items["properties"]["a"]["type"], "integer");
+        assert_eq!(items["properties"]["b"]["type"], "boolean");
+        assert_eq!(items["required"], json!([]));
+    }
+
+    #[test]
+    fn different_types_accept_any_value() {
+        let schema = infer(json!([{ "id": 1 }, { "id": "a" }]));
+
+        assert_eq!(schema["properties"]["id"]["type"], "none");
+    }
+
+    #[test]
+    fn samples_must_be_objects() {
+        assert!(infer_schema(&[]).is_err());
+        assert_eq!(
+            infer_schema(&[json!({}), json!([])]).unwrap_err(),
+            "example 2 is not an object, only objects can become a struct"
+        );
+    }
+}
@@ -0,0 +1,526 @@
+/// JSON Type Definition module
+///
+/// Lowers a JSON Type Definition (RFC 8927) schema into the same [`JsonSchema`] model, so it goes
+/// through the same generator as the macro and the JSON Schema files
+///
+/// # Forms
+/// - `properties` and `optionalProperties` become a struct, the optional ones are an `Option`
+/// - `elements` becomes a `Vec`, and `values` a `BTreeMap` with `String` keys
+/// - `enum` becomes a `String` with the values in it's schema, like a string `enum` of the macro,
+///   the Rust type doesn't check them, a validator using the schema does
+/// - `discriminator` and `mapping` become an enum, tagged by the discriminator
+/// - `ref` points to one of the `definitions`, an object keeps the name of it's definition
+/// - The empty form accepts anything, it's a `serde_json::Value`
+///
+/// # Types
+/// `timestamp` is a `date-time` string, the floats are a number, and the ints are an integer,
+/// the unsigned ones with their bounds
+use indexmap::IndexMap;
+use serde_json::{Map, Value};
+
+use crate::{
+    checkers::closest_match,
+    models::{Discriminator, Formats, JsonSchema, JsonSchemaTypes, JsonSchemaValues},
+};
+
+/// the keywords a schema can have, `definitions` is only allowed at the root
+const KEYWORDS: [&str; 13] = [
+    "definitions",
+    "nullable",
+    "metadata",
+    "ref",
+    "type",
+    "enum",
+    "elements",
+    "properties",
+    "optionalProperties",
+    "additionalProperties",
+    "values",
+    "discriminator",
+    "mapping",
+];
+
+/// the keywords that decide the form of a schema, a schema has one form at most
+const FORMS: [&str; 7] = [
+    "ref",
+    "type",
+    "enum",
+    "elements",
+    "properties",
+    "values",
+    "discriminator",
+];
+
+/// Why a schema can't be lowered, with where it is in the document, like `properties.id`
+pub struct JtdError {
+    pub location: String,
+    pub message: String,
+}
+
+/// Lowers a JSON Type Definition schema
+///
+/// # Errors
+/// - A keyword or a type isn't known
+/// - A schema has more than one form
+/// - A `ref` isn't in the `definitions`, or it references itself
+/// - A `mapping` schema isn't of the properties form
+pub fn lower(document: &Value) -> Result<JsonSchema, JtdError> {
+    let definitions = match document.get("definitions") {
+        Some(Value::Object(definitions)) => definitions.clone(),
+        Some(_) => {
+            return Err(JtdError {
+                location: "definitions".to_owned(),
+                message: "the definitions must be an object".to_owned(),
+            })
+        }
+        None => Map::new(),
+    };
+
+    let lowering = Lowering {
+        definitions,
+        stack: Vec::new(),
+    };
+
+    lowering.lower(document, "", true)
+}
+
+struct Lowering {
+    definitions: Map<String, Value>,
+
+    /// the definitions being lowered, a `ref` to one of them is recursive
+    stack: Vec<String>,
+}
+
+impl Lowering {
+    fn lower(&self, schema: &Value, location: &str, root: bool) -> Result<JsonSchema, JtdError> {
+        let error = |key: &str, message: String| JtdError {
+            location: join(location, key),
+            message,
+        };
+
+        let Some(object) = schema.as_object() else {
+            return Err(error("", "a schema must be an object".to_owned()));
+        };
+
+        for key in object.keys() {
+            if !KEYWORDS.contains(&key.as_str()) {
+                let message = match closest_match(key, &KEYWORDS) {
+                    Some(suggestion) => {
+                        format!("unknown keyword `{}`, did you mean `{}`?", key, suggestion)
+                    }
+                    None => format!("unknown keyword `{}`", key),
+                };
+
+                return Err(error(key, message));
+            }
+        }
+
+        if !root && object.contains_key("definitions") {
+            return Err(error(
+                "definitions",
+                "only the root schema can have `definitions`".to_owned(),
+            ));
+        }
+
+        // `optionalProperties` alone is still the properties form
+        let forms = FORMS
+            .into_iter()
+            .filter(|form| {
+                object.contains_key(*form)
+                    || *form == "properties" && object.contains_key("optionalProperties")
+            })
+            .collect::<Vec<_>>();
+
+        if let [first, second, ..] = forms[..] {
+            return Err(error(
+                "",
+                format!("a schema can't have both `{}` and `{}`", first, second),
+            ));
+        }
+
+        let mut lowered = match forms.first().copied() {
+            Some("ref") => self.reference(&object["ref"], location)?,
+            Some("type") => primitive(&object["type"]).map_err(|message| error("type", message))?,
+            Some("enum") => JsonSchema {
+                ty: JsonSchemaTypes::String,
+                enum_values: Some(
+                    object["enum"]
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|value| match value {
+                            Value::String(value) => Ok(JsonSchemaValues::Str(value.clone())),
+                            _ => Err(error("enum", "every value must be a string".to_owned())),
+                        })
+                        .collect::<Result<_, _>>()?,
+                ),
+                ..Default::default()
+            },
+            Some("elements") => JsonSchema {
+                ty: JsonSchemaTypes::Array,
+                items: Some(Box::new(self.lower(
+                    &object["elements"],
+                    &join(location, "elements"),
+                    false,
+                )?)),
+                ..Default::default()
+            },
+            Some("properties") => self.properties(object, location)?,
+            Some("values") => JsonSchema {
+                ty: JsonSchemaTypes::Object,
+                additional_properties: Some(Box::new(self.lower(
+                    &object["values"],
+                    &join(location, "values"),
+                    false,
+                )?)),
+                ..Default::default()
+            },
+            Some("discriminator") => self.discriminator(object, location)?,
+            // the empty form
+            _ => JsonSchema::default(),
+        };
+
+        if object.get("nullable") == Some(&Value::Bool(true)) {
+            lowered.nullable = Some(true);
+        }
+
+        if let Some(description) = object
+            .get("metadata")
+            .and_then(|metadata| metadata.get("description"))
+            .and_then(Value::as_str)
+        {
+            lowered.description = Some(description.to_owned());
+        }
+
+        Ok(lowered)
+    }
+
+    fn reference(&self, reference: &Value, location: &str) -> Result<JsonSchema, JtdError> {
+        let location = join(location, "ref");
+
+        let Some(name) = reference.as_str() else {
+            return Err(JtdError {
+                location,
+                message: "a `ref` is the name of a definition".to_owned(),
+            });
+        };
+
+        let Some(definition) = self.definitions.get(name) else {
+            return Err(JtdError {
+                location,
+                message: format!("`{}` isn't in the definitions", name),
+            });
+        };
+
+        if self.stack.iter().any(|definition| definition == name) {
+            return Err(JtdError {
+                location,
+                message: format!(
+                    "`{}` references itself through `{}`, recursive types aren't supported",
+                    name,
+                    self.stack.join("` -> `")
+                ),
+            });
+        }
+
+        let mut stack = self.stack.clone();
+        stack.push(name.to_owned());
+
+        let lowering = Lowering {
+            definitions: self.definitions.clone(),
+            stack,
+        };
+
+        let mut schema = lowering.lower(definition, &format!("definitions.{}", name), false)?;
+
+        // it's generated once however many times it's referenced
+        if matches!(schema.ty, JsonSchemaTypes::Object) && !schema.is_map()
+            || schema.one_of.is_some()
+        {
+            schema.struct_name = Some(name.to_owned());
+        }
+
+        Ok(schema)
+    }
+
+    fn properties(
+        &self,
+        object: &Map<String, Value>,
+        location: &str,
+    ) -> Result<JsonSchema, JtdError> {
+        let mut properties = IndexMap::new();
+        let mut required = Vec::new();
+
+        for (key, is_required) in [("properties", true), ("optionalProperties", false)] {
+            let Some(declared) = object.get(key) else {
+                continue;
+            };
+
+            let Some(declared) = declared.as_object() else {
+                return Err(JtdError {
+                    location: join(location, key),
+                    message: format!("the `{}` must be an object", key),
+                });
+            };
+
+            for (name, schema) in declared {
+                let property_location = join(&join(location, key), name);
+
+                if properties.contains_key(name) {
+                    return Err(JtdError {
+                        location: property_location,
+                        message: format!("`{}` is both required and optional", name),
+                    });
+                }
+
+                properties.insert(name.clone(), self.lower(schema, &property_location, false)?);
+
+                if is_required {
+                    required.push(name.clone());
+                }
+            }
+        }
+
+        Ok(JsonSchema {
+            ty: JsonSchemaTypes::Object,
+            properties: Some(properties),
+            required: Some(required),
+            ..Default::default()
+        })
+    }
+
+    fn discriminator(
+        &self,
+        object: &Map<String, Value>,
+        location: &str,
+    ) -> Result<JsonSchema, JtdError> {
+        let Some(tag) = object["discriminator"].as_str() else {
+            return Err(JtdError {
+                location: join(location, "discriminator"),
+                message: "the `discriminator` is the name of the tag property".to_owned(),
+            });
+        };
+
+        let Some(mapping) = object.get("mapping").and_then(Value::as_object) else {
+            return Err(JtdError {
+                location: join(location, "mapping"),
+                message: "a `discriminator` needs a `mapping` object".to_owned(),
+            });
+        };
+
+        let mut variants = Vec::new();
+
+        for (value, schema) in mapping {
+            let variant_location = join(&join(location, "mapping"), value);
+
+            let mut variant = self.lower(schema, &variant_location, false)?;
+
+            if !matches!(variant.ty, JsonSchemaTypes::Object) || variant.is_map() {
+                return Err(JtdError {
+                    location: variant_location,
+                    message: "every schema of a `mapping` must be of the properties form"
+                        .to_owned(),
+                });
+            }
+
+            if variant.nullable == Some(true) {
+                return Err(JtdError {
+                    location: variant_location,
+                    message: "a schema of a `mapping` can't be `nullable`".to_owned(),
+                });
+            }
+
+            let properties = variant.properties.get_or_insert_with(Default::default);
+
+            if properties.contains_key(tag) {
+                return Err(JtdError {
+                    location: variant_location,
+                    message: format!("`{}` is the tag, it can't be a property too", tag),
+                });
+            }
+
+            // the tag is what tells the variants apart, it's named after it's value
+            properties.insert(
+                tag.to_owned(),
+                JsonSchema {
+                    ty: JsonSchemaTypes::String,
+                    const_value: Some(JsonSchemaValues::Str(value.clone())),
+                    ..Default::default()
+                },
+            );
+            variant.struct_name = Some(value.clone());
+
+            variants.push(variant);
+        }
+
+        Ok(JsonSchema {
+            one_of: Some(variants),
+            discriminator: Some(Discriminator {
+                property_name: tag.to_owned(),
+                mapping: IndexMap::new(),
+            }),
+            ..Default::default()
+        })
+    }
+}
+
+// the schema of a `type`
+fn primitive(ty: &Value) -> Result<JsonSchema, String> {
+    let (ty, format, maximum) = match ty.as_str() {
+        Some("boolean") => (JsonSchemaTypes::Boolean, None, None),
+        Some("string") => (JsonSchemaTypes::String, None, None),
+        Some("timestamp") => (JsonSchemaTypes::String, Some(Formats::DateTime), None),
+        Some("float32" | "float64") => (JsonSchemaTypes::Number, None, None),
+        Some("int8" | "int16" | "int32") => (JsonSchemaTypes::Integer, None, None),
+        Some("uint8") => (JsonSchemaTypes::Integer, None, Some(u8::MAX.into())),
+        Some("uint16") => (JsonSchemaTypes::Integer, None, Some(u16::MAX.into())),
+        Some("uint32") => (JsonSchemaTypes::Integer, None, Some(u32::MAX.into())),
+        Some(ty) => {
+            return Err(format!(
+                "unknown type `{}`, expected one of `boolean`, `string`, `timestamp`, `float32`, `float64`, `int8`, `uint8`, `int16`, `uint16`, `int32` or `uint32`",
+                ty
+            ))
+        }
+        None => return Err("the `type` must be a string".to_owned()),
+    };
+
+    Ok(JsonSchema {
+        ty,
+        format,
+        minimum: maximum.as_ref().map(|_| 0.into()),
+        maximum,
+        ..Default::default()
+    })
+}
+
+pub(crate) fn join(location: &str, key: &str) -> String {
+    match (location.is_empty(), key.is_empty()) {
+        (true, _) => key.to_owned(),
+        (_, true) => location.to_owned(),
+        _ => format!("{}.{}", location, key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn error(document: Value) -> (String, String) {
+        let error = lower(&document).err().unwrap();
+        (error.location, error.message)
+    }
+
+    #[test]
+    fn properties_form() {
+        let schema = lower(&json!({
+            "properties": {
+                "id": { "type": "uint8" },
+                "created": { "type": "timestamp" }
+            },
+            "optionalProperties": {
+                "tags": { "elements": { "type": "string" } },
+                "scores": { "values": { "type": "float64" }, "nullable": true }
+            }
+        }))
+        .ok()
+        .unwrap();
+
+        let properties = schema.properties.as_ref().unwrap();
+
+        assert_eq!(
+            schema.required,
+            Some(vec!["id".to_owned(), "created".to_owned()])
+        );
+        assert_eq!(properties["id"].minimum, Some(0.into()));
+        assert_eq!(properties["id"].maximum, Some(255.into()));
+        assert!(matches!(
+            properties["created"].format,
+            Some(Formats::DateTime)
+        ));
+        assert!(matches!(properties["tags"].ty, JsonSchemaTypes::Array));
+        assert!(properties["scores"].is_map());
+        assert_eq!(properties["scores"].nullable, Some(true));
+    }
+
+    #[test]
+    fn enum_is_a_string_with_the_values() {
+        let schema = lower(&json!({
+            "properties": { "role": { "enum": ["admin", "member"] } }
+        }))
+        .ok()
+        .unwrap();
+
+        let role = &schema.properties.unwrap()["role"];
+
+        assert!(matches!(role.ty, JsonSchemaTypes::String));
+        assert!(matches!(
+            role.enum_values.as_deref(),
+            Some([JsonSchemaValues::Str(admin), JsonSchemaValues::Str(member)])
+                if admin == "admin" && member == "member"
+        ));
+    }
+
+    #[test]
+    fn discriminator_is_tagged() {
+        let schema = lower(&json!({
+            "properties": {
+                "shape": {
+                    "discriminator": "kind",
+                    "mapping": {
+                        "circle": { "properties": { "radius": { "type": "float32" } } },
+                        "square": { "ref": "square" }
+                    }
+                }
+            },
+            "definitions": {
+                "square": { "properties": { "side": { "type": "float32" } } }
+            }
+        }))
+        .ok()
+        .unwrap();
+
+        let shape = &schema.properties.unwrap()["shape"];
+        let variants = shape.one_of.as_ref().unwrap();
+
+        assert_eq!(shape.discriminator.as_ref().unwrap().property_name, "kind");
+        assert_eq!(variants[0].struct_name.as_deref(), Some("circle"));
+        assert!(matches!(
+            &variants[1].properties.as_ref().unwrap()["kind"].const_value,
+            Some(JsonSchemaValues::Str(tag)) if tag == "square"
+        ));
+    }
+
+    #[test]
+    fn invalid_schemas() {
+        assert_eq!(
+            error(json!({ "properties": { "id": { "typ": "string" } } })),
+            (
+                "properties.id.typ".to_owned(),
+                "unknown keyword `typ`, did you mean `type`?".to_owned()
+            )
+        );
+        assert_eq!(
+            error(json!({ "type": "string", "enum": ["a"] })),
+            (
+                "".to_owned(),
+                "a schema can't have both `type` and `enum`".to_owned()
+            )
+        );
+        assert_eq!(
+            error(
+                json!({ "properties": { "next": { "ref": "node" } }, "definitions": { "node": { "properties": { "next": { "ref": "node" } } } } })
+            ),
+            (
+                "definitions.node.properties.next.ref".to_owned(),
+                "`node` references itself through `node`, recursive types aren't supported"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            error(json!({ "properties": { "id": { "type": "int64" } } })).0,
+            "properties.id.type"
+        );
+    }
+}
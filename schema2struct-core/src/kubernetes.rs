@@ -0,0 +1,553 @@
+/// Kubernetes module
+///
+/// Imports the `openAPIV3Schema` of a `CustomResourceDefinition` manifest, the `spec` and the
+/// `status` of the resource become `{Kind}Spec` and `{Kind}Status`, so a controller can work on
+/// typed custom resources
+///
+/// # Extensions
+/// - `x-kubernetes-int-or-string` becomes the `IntOrString` enum, shared by every field using it
+/// - `x-kubernetes-preserve-unknown-fields` and `x-kubernetes-embedded-resource` without any
+///   `properties` become a `serde_json::Value`, with properties it's a struct and the unknown
+///   fields are left out
+/// - A map of `additionalProperties` becomes a `BTreeMap`, and any other object without
+///   `properties` is kept as a `serde_json::Value` too, so nothing in it is lost
+/// - The other extensions, like `x-kubernetes-list-type`, only matter to the API server
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use serde_json::Value;
+use syn::parse::{Parse, ParseStream};
+
+use crate::{
+    checkers::closest_match,
+    files::{
+        manifest_path, parse_input, parse_schema, read_document, rebuilt_on_change, string_value,
+        FileError, FileErrorKind, InputOptions,
+    },
+    generator::{generate_all, Options},
+    models::{JsonSchema, JsonSchemaTypes},
+    naming::type_ident,
+};
+
+/// the name of the type every `x-kubernetes-int-or-string` becomes
+const INT_OR_STRING: &str = "IntOrString";
+
+/// A `CustomResourceDefinition`, with the schema of every version it declares
+pub struct CustomResourceDefinition {
+    path: PathBuf,
+    kind: String,
+
+    /// the versions in the order they're declared, with their `openAPIV3Schema`
+    versions: Vec<CrdVersion>,
+}
+
+struct CrdVersion {
+    name: String,
+    storage: bool,
+    schema: Option<Value>,
+}
+
+impl CustomResourceDefinition {
+    /// Reads a `CustomResourceDefinition` manifest, written in YAML or JSON by it's extension
+    ///
+    /// # Errors
+    /// - The file can't be read or parsed
+    /// - It's not a `CustomResourceDefinition`, or it has no kind or versions
+    pub fn read(path: &Path) -> Result<Self, FileError> {
+        let document = read_document(path)?;
+
+        let invalid = |location: &str, message: String| FileError {
+            path: path.to_owned(),
+            kind: FileErrorKind::Invalid {
+                location: location.to_owned(),
+                message,
+            },
+        };
+
+        match document.get("kind").and_then(Value::as_str) {
+            Some("CustomResourceDefinition") => {}
+            Some(kind) => {
+                return Err(invalid(
+                    "kind",
+                    format!(
+                        "it's a `{}`, only a `CustomResourceDefinition` can be imported",
+                        kind
+                    ),
+                ))
+            }
+            None => {
+                return Err(invalid(
+                    "kind",
+                    "it's not a Kubernetes manifest, there's no `kind`".to_owned(),
+                ))
+            }
+        }
+
+        let Some(kind) = document.pointer("/spec/names/kind").and_then(Value::as_str) else {
+            return Err(invalid(
+                "spec.names.kind",
+                "the resource needs a `kind`".to_owned(),
+            ));
+        };
+
+        // `apiextensions.k8s.io/v1beta1` has a single schema for every version
+        let shared_schema = document.pointer("/spec/validation/openAPIV3Schema");
+
+        let versions = document
+            .pointer("/spec/versions")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|version| {
+                Some(CrdVersion {
+                    name: version.get("name")?.as_str()?.to_owned(),
+                    storage: version.get("storage") == Some(&Value::Bool(true)),
+                    schema: version
+                        .pointer("/schema/openAPIV3Schema")
+                        .or(shared_schema)
+                        .cloned(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if versions.is_empty() {
+            return Err(invalid(
+                "spec.versions",
+                "the resource needs at least one version".to_owned(),
+            ));
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            kind: kind.to_owned(),
+            versions,
+        })
+    }
+
+    /// The `kind` of the resource, like `CronTab`
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// The names of every version, in the order they're declared
+    pub fn version_names(&self) -> impl Iterator<Item = &str> {
+        self.versions.iter().map(|version| version.name.as_str())
+    }
+
+    /// Gets the `spec` and the `status` schemas of a version, ready to be generated
+    ///
+    /// # Arguments
+    /// - `version`: The version to import, the storage version if it's `None`
+    ///
+    /// # Errors
+    /// - There's no such version, or it has no schema
+    /// - The schema isn't valid, or it's `spec` isn't an object with `properties`
+    pub fn schemas(&self, version: Option<&str>) -> Result<Vec<JsonSchema>, FileError> {
+        let selected = match version {
+            Some(name) => self.versions.iter().find(|version| version.name == name),
+            None => self
+                .versions
+                .iter()
+                .find(|version| version.storage)
+                .or(self.versions.first()),
+        };
+
+        let Some(selected) = selected else {
+            let name = version.unwrap_or_default();
+            let names = self.version_names().collect::<Vec<_>>();
+
+            let message = match closest_match(name, &names) {
+                Some(suggestion) => {
+                    format!(
+                        "there's no `{}` version, did you mean `{}`?",
+                        name, suggestion
+                    )
+                }
+                None => format!(
+                    "there's no `{}` version, expected one of `{}`",
+                    name,
+                    names.join("`, `")
+                ),
+            };
+
+            return Err(self.invalid("spec.versions", message));
+        };
+
+        let location = format!("spec.versions.{}.schema.openAPIV3Schema", selected.name);
+
+        let Some(mut schema) = selected.schema.clone() else {
+            return Err(self.invalid(&location, "the version has no schema"));
+        };
+
+        lower(&mut schema);
+
+        let mut schema =
+            parse_schema(schema).map_err(|e| self.invalid(&location, e.to_string()))?;
+        name_int_or_string(&mut schema);
+
+        let mut properties = schema.properties.take().unwrap_or_default();
+        let mut schemas = Vec::new();
+
+        for (property, required) in [("spec", true), ("status", false)] {
+            let Some(mut root) = properties.shift_remove(property) else {
+                if required {
+                    return Err(self.invalid(&location, "the resource has no `spec`"));
+                }
+
+                continue;
+            };
+
+            // a `status` that preserves every field has nothing to generate
+            if !matches!(root.ty, JsonSchemaTypes::Object) {
+                if required {
+                    return Err(self.invalid(
+                        &format!("{}.properties.{}", location, property),
+                        "only an object with `properties` can become a type",
+                    ));
+                }
+
+                continue;
+            }
+
+            root.struct_name =
+                Some(type_ident(None, &format!("{} {}", self.kind, property)).to_string());
+            root.depth = 1;
+
+            schemas.push(root);
+        }
+
+        Ok(schemas)
+    }
+
+    fn invalid(&self, location: &str, message: impl Into<String>) -> FileError {
+        FileError {
+            path: self.path.clone(),
+            kind: FileErrorKind::Invalid {
+                location: location.to_owned(),
+                message: message.into(),
+            },
+        }
+    }
+}
+
+// turns the Kubernetes extensions into what the model can hold
+fn lower(schema: &mut Value) {
+    let Some(object) = schema.as_object_mut() else {
+        return;
+    };
+
+    let flag = |key: &str| object.get(key) == Some(&Value::Bool(true));
+
+    if flag("x-kubernetes-int-or-string") {
+        let description = object.get("description").cloned();
+
+        *schema = serde_json::json!({
+            "oneOf": [{ "type": "integer" }, { "type": "string" }],
+        });
+
+        if let Some(description) = description {
+            schema["description"] = description;
+        }
+
+        return;
+    }
+
+    let preserved =
+        flag("x-kubernetes-preserve-unknown-fields") || flag("x-kubernetes-embedded-resource");
+
+    // a map of `additionalProperties` keeps the type of it's values
+    if !preserved && !object.contains_key("properties") {
+        if let Some(values @ Value::Object(_)) = object.get_mut("additionalProperties") {
+            lower(values);
+
+            return;
+        }
+    }
+
+    let free_form = preserved || object.get("type").is_some_and(|ty| ty == "object");
+
+    // an object without properties can hold anything, it's kept as any value
+    if free_form && !object.contains_key("properties") {
+        object.remove("type");
+        object.remove("additionalProperties");
+
+        return;
+    }
+
+    if let Some(Value::Object(properties)) = object.get_mut("properties") {
+        properties.values_mut().for_each(lower);
+    }
+
+    if let Some(items) = object.get_mut("items") {
+        lower(items);
+    }
+}
+
+// every `x-kubernetes-int-or-string` is the same type, so they all get the same name
+fn name_int_or_string(schema: &mut JsonSchema) {
+    if let Some([int, string]) = schema.one_of.as_deref_mut() {
+        if matches!(int.ty, JsonSchemaTypes::Integer)
+            && matches!(string.ty, JsonSchemaTypes::String)
+        {
+            int.struct_name = Some("Int".to_owned());
+            string.struct_name = Some("String".to_owned());
+
+            schema.struct_name = Some(INT_OR_STRING.to_owned());
+
+            // the description differs between the fields using it, it's left to them
+            schema.description = None;
+
+            return;
+        }
+    }
+
+    for property in schema.properties.iter_mut().flat_map(|p| p.values_mut()) {
+        name_int_or_string(property);
+    }
+
+    if let Some(items) = &mut schema.items {
+        name_int_or_string(items);
+    }
+}
+
+/// The input of `schema2struct!(crd = "deploy/crontab.yaml", version = "v1")`
+///
+/// the storage version is imported if there's no `version`
+pub struct CrdInput {
+    pub path: syn::LitStr,
+    pub version: Option<syn::LitStr>,
+    pub options: InputOptions,
+}
+
+impl Parse for CrdInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path = None;
+        let mut version = None;
+
+        let options = parse_input(input, &["crd", "version"], |key, input| {
+            match key.to_string().as_str() {
+                "crd" => {
+                    path = Some(string_value(
+                        input,
+                        "`crd` is the path of the manifest, like `\"crd.yaml\"`",
+                    )?)
+                }
+                _ => {
+                    version = Some(string_value(
+                        input,
+                        "`version` is the name of a version, like `\"v1\"`",
+                    )?)
+                }
+            }
+
+            Ok(())
+        })?;
+
+        let Some(path) = path else {
+            return Err(input.error("`crd = \"...\"` is required"));
+        };
+
+        Ok(Self {
+            path,
+            version,
+            options,
+        })
+    }
+}
+
+/// Whether the macro input is a `CustomResourceDefinition` import, it starts with `crd`
+pub fn is_crd_input(tokens: &TokenStream) -> bool {
+    matches!(
+        tokens.clone().into_iter().next(),
+        Some(proc_macro2::TokenTree::Ident(ident)) if ident == "crd"
+    )
+}
+
+/// Generates the `spec` and `status` types of a `CustomResourceDefinition`, the path is relative
+/// to the crate's `Cargo.toml`
+///
+/// # Errors
+/// - The input is invalid
+/// - The manifest can't be read, or the version can't be generated
+pub fn expand_crd(tokens: TokenStream, options: &Options) -> syn::Result<TokenStream> {
+    let input = syn::parse2::<CrdInput>(tokens)?;
+    let path = manifest_path(&input.path);
+
+    let crd =
+        CustomResourceDefinition::read(&path).map_err(|e| syn::Error::new(input.path.span(), e))?;
+
+    let schemas = crd
+        .schemas(input.version.as_ref().map(syn::LitStr::value).as_deref())
+        .map_err(|e| {
+            let span = input.version.as_ref().unwrap_or(&input.path).span();
+            syn::Error::new(span, e)
+        })?;
+
+    let output = generate_all(&schemas, &input.options.apply(options))?;
+
+    Ok(rebuilt_on_change(&path, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::tests::temp_file;
+
+    const MANIFEST: &str = r#"
+apiVersion: apiextensions.k8s.io/v1
+kind: CustomResourceDefinition
+spec:
+  names: { kind: CronTab }
+  versions:
+    - name: v1beta1
+      storage: false
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            spec:
+              type: object
+              properties:
+                cron: { type: string }
+    - name: v1
+      storage: true
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            spec:
+              type: object
+              required: [cronSpec]
+              properties:
+                cronSpec: { type: string }
+                replicas: { x-kubernetes-int-or-string: true, description: how many }
+                labels: { type: object, additionalProperties: { type: string } }
+                template: { type: object, x-kubernetes-preserve-unknown-fields: true }
+            status:
+              type: object
+              properties:
+                lastScheduleTime: { type: string }
+    - name: v2alpha1
+      schema:
+        openAPIV3Schema:
+          type: object
+          properties:
+            status: { type: object }
+"#;
+
+    fn crd(name: &str, manifest: &str) -> CustomResourceDefinition {
+        CustomResourceDefinition::read(&temp_file(name, manifest)).unwrap()
+    }
+
+    fn property_names(schema: &JsonSchema) -> Vec<&str> {
+        schema
+            .properties
+            .iter()
+            .flat_map(|properties| properties.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn message(error: FileError) -> (String, String) {
+        match error.kind {
+            FileErrorKind::Invalid { location, message } => (location, message),
+            kind => panic!("expected an invalid manifest, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn the_storage_version_is_the_default() {
+        let crd = crd("storage.yaml", MANIFEST);
+        let schemas = crd.schemas(None).unwrap();
+
+        assert_eq!(
+            schemas
+                .iter()
+                .map(|schema| schema.struct_name.as_deref().unwrap())
+                .collect::<Vec<_>>(),
+            ["CronTabSpec", "CronTabStatus"]
+        );
+        assert_eq!(
+            property_names(&schemas[0]),
+            ["cronSpec", "replicas", "labels", "template"]
+        );
+
+        let schemas = crd.schemas(Some("v1beta1")).unwrap();
+
+        assert_eq!(schemas.len(), 1);
+        assert_eq!(property_names(&schemas[0]), ["cron"]);
+    }
+
+    #[test]
+    fn the_first_version_is_the_default_without_a_storage_one() {
+        let crd = crd(
+            "first.yaml",
+            &MANIFEST.replace("storage: true", "storage: false"),
+        );
+
+        assert_eq!(property_names(&crd.schemas(None).unwrap()[0]), ["cron"]);
+    }
+
+    #[test]
+    fn a_version_has_to_exist_and_have_a_spec() {
+        let crd = crd("versions.yaml", MANIFEST);
+
+        assert_eq!(
+            message(crd.schemas(Some("v2")).err().unwrap()),
+            (
+                "spec.versions".to_owned(),
+                "there's no `v2` version, did you mean `v1`?".to_owned()
+            )
+        );
+        assert_eq!(
+            message(crd.schemas(Some("v2alpha1")).err().unwrap()),
+            (
+                "spec.versions.v2alpha1.schema.openAPIV3Schema".to_owned(),
+                "the resource has no `spec`".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn extensions_are_lowered() {
+        let spec = crd("extensions.yaml", MANIFEST)
+            .schemas(Some("v1"))
+            .unwrap()
+            .remove(0);
+        let properties = spec.properties.as_ref().unwrap();
+
+        let replicas = &properties["replicas"];
+        let variants = replicas.one_of.as_ref().unwrap();
+
+        assert_eq!(replicas.struct_name.as_deref(), Some(INT_OR_STRING));
+        assert!(matches!(variants[0].ty, JsonSchemaTypes::Integer));
+        assert!(matches!(variants[1].ty, JsonSchemaTypes::String));
+
+        assert!(properties["labels"].is_map());
+        assert!(matches!(properties["template"].ty, JsonSchemaTypes::None));
+
+        // only `cronSpec` is required
+        assert_eq!(spec.required, Some(vec!["cronSpec".to_owned()]));
+    }
+
+    #[test]
+    fn a_shared_v1beta1_schema_applies_to_every_version() {
+        let crd = crd(
+            "shared.yaml",
+            r#"
+kind: CustomResourceDefinition
+spec:
+  names: { kind: Backup }
+  validation:
+    openAPIV3Schema:
+      type: object
+      properties:
+        spec: { type: object, properties: { target: { type: string } } }
+  versions:
+    - { name: v1alpha1, storage: true }
+"#,
+        );
+
+        assert_eq!(property_names(&crd.schemas(None).unwrap()[0]), ["target"]);
+    }
+}
@@ -0,0 +1,53 @@
+//! # schema2struct-core
+//!
+//! The parser, checkers and generator behind the `schema2struct!` macro, usable outside of a
+//! proc-macro, like from a `build.rs`, a command-line tool or tests
+//!
+//! Schemas come either from the macro DSL with [`parse`], or from JSON Schema files with [`read_schema_file`],
+//! written in JSON, YAML or TOML, along with JSON Type Definitions and Avro schemas
+//!
+//! # Example
+//! ```ignore
+//! use schema2struct_core::{generate, parse, Options};
+//!
+//! let schema = parse(quote::quote! {
+//!     struct: User,
+//!     type: object,
+//!     properties: {
+//!         "name": { type: string }
+//!     }
+//! })?;
+//!
+//! let code = generate(&schema, &Options::from(&schema))?;
+//! ```
+#[macro_use]
+mod diagnostics;
+
+pub mod avro;
+pub mod checkers;
+mod derive;
+pub mod files;
+mod generator;
+pub mod infer;
+pub mod jtd;
+pub mod kubernetes;
+pub mod models;
+pub mod naming;
+pub mod openapi;
+mod parsers;
+mod try_from_impls;
+pub mod typescript;
+
+pub use derive::{derive_has_json_schema, schema2struct_attr};
+pub use files::{read_schema_file, FileError};
+pub use generator::{generate, generate_all, generate_all_indexed, Options};
+pub use infer::infer_schema;
+pub use models::JsonSchema;
+
+/// Parses the schema DSL and runs every check on it
+///
+/// # Returns
+/// The parsed schema, or a single error combining every problem found in it
+pub fn parse(tokens: proc_macro2::TokenStream) -> syn::Result<JsonSchema> {
+    diagnostics::collect(|| syn::parse2::<JsonSchema>(tokens))
+}
@@ -0,0 +1,735 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{Map, Number, Value};
+
+/// used to identify what type is current schema
+///
+/// ```ignore
+///
+/// jsonschema!{
+///     type: object,
+///     ...
+/// }
+/// ```
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonSchemaTypes {
+    Object,
+    String,
+    Array,
+    Number,
+    Integer,
+    Boolean,
+
+    // we make it the default so to know if it's fresh with ::default or has already been set
+    #[default]
+    None,
+}
+
+/// tells the `oneOf` schemas apart by one of their properties
+///
+/// ```yaml
+/// oneOf:
+///   - $ref: "#/components/schemas/Cat"
+///   - $ref: "#/components/schemas/Dog"
+/// discriminator:
+///   propertyName: petType
+///   mapping:
+///     cat: "#/components/schemas/Cat"
+/// ```
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Discriminator {
+    pub property_name: String,
+
+    // the tag of every schema, a schema that's not in it is tagged with it's own name
+    #[serde(default)]
+    pub mapping: IndexMap<String, String>,
+}
+
+/// the Avro type a schema was lowered from, so it's exported back the same
+#[derive(Clone, Debug)]
+pub enum AvroType {
+    /// a primitive or a logical type, like `"int"` or `{ "type": "long", "logicalType": "timestamp-millis" }`,
+    /// it's written back as is
+    Primitive(Value),
+
+    /// a `record`, `enum` or `fixed` by it's full name, like `com.example.User`, with the attributes
+    /// the model doesn't hold, like the `size` of a `fixed` or the `aliases`
+    Named {
+        full_name: String,
+        attributes: Map<String, Value>,
+    },
+}
+
+/// the main struct holding all the data about every root and nested schemas
+/// creates the schema struct but adds the *_span for every key
+///
+/// it also puts the depth field and current_key_span filed
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonSchema {
+    // a schema without a type accepts any value
+    #[serde(rename = "type", default)]
+    pub ty: JsonSchemaTypes,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<JsonSchemaValues>,
+
+    // any value can be an example, the macro only takes strings
+    #[serde(rename = "examples")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub examples: Option<Vec<Value>>,
+
+    #[serde(rename = "enum")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enum_values: Option<Vec<JsonSchemaValues>>,
+
+    #[serde(rename = "const")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub const_value: Option<JsonSchemaValues>,
+
+    // keeps the declaration order, so the generated fields and json are deterministic
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub properties: Option<IndexMap<String, JsonSchema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required: Option<Vec<String>>,
+
+    #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
+    pub min_lenght: Option<usize>,
+
+    #[serde(rename = "maxLength", skip_serializing_if = "Option::is_none")]
+    pub max_lenght: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Formats>,
+
+    // a bound can be negative or a float, like `minimum: -0.5`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<Number>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<Number>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Box<JsonSchema>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_items: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unique_items: Option<bool>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contains: Option<Box<JsonSchema>>,
+
+    // the schema of every property that's not in `properties`, an object with only this is a map,
+    // `additionalProperties: true` and `false` don't change the type, so they're left out
+    #[serde(
+        default,
+        deserialize_with = "deserialize_additional_properties",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub additional_properties: Option<Box<JsonSchema>>,
+
+    // the value can also be `null`, it's how the inferred and OpenAPI schemas mark it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
+    // the value is one of these schemas, it becomes an enum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub one_of: Option<Vec<JsonSchema>>,
+
+    // OpenAPI's way of telling the `one_of` schemas apart, the tags end up as `const` values
+    #[serde(skip_serializing)]
+    pub discriminator: Option<Discriminator>,
+
+    // the objects it's made of, like a component extending another, they're merged into it before
+    // generating, so it's never serialized
+    #[serde(skip_serializing)]
+    pub all_of: Option<Vec<JsonSchema>>,
+
+    // a reference to another schema, it's resolved before generating, so it's never serialized
+    #[serde(rename = "$ref", skip_serializing)]
+    pub reference: Option<String>,
+
+    // the Avro type it was lowered from, it's not part of the json schema
+    #[serde(skip)]
+    pub avro: Option<AvroType>,
+
+    // generation options, they are not part of the json schema
+    #[serde(skip)]
+    pub derives: Option<Vec<syn::Path>>,
+    #[serde(skip)]
+    pub attrs: Option<Vec<syn::Attribute>>,
+    #[serde(skip)]
+    pub vis: Option<syn::Visibility>,
+    #[serde(skip)]
+    pub module: Option<syn::Ident>,
+    #[serde(skip)]
+    pub rename_all: Option<RenameAll>,
+    #[serde(skip)]
+    pub from_example: Option<Vec<String>>,
+
+    // tracking fields
+    #[serde(skip)]
+    pub depth: usize,
+    #[serde(skip)]
+    pub current_key_span: Option<proc_macro2::Span>,
+    #[serde(skip)]
+    pub struct_name: Option<String>,
+    #[serde(skip)]
+    pub struct_name_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub ty_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub title_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub description_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub default_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub examples_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub enum_values_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub const_value_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub properties_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub required_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub min_lenght_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub max_lenght_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub pattern_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub format_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub minimum_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub maximum_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub items_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub min_items_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub max_items_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub unique_items_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub contains_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub derives_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub attrs_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub vis_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub module_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub rename_all_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+    #[serde(skip)]
+    pub from_example_span: Option<(proc_macro2::Span, proc_macro2::Span)>,
+}
+
+/// holds the different uses of the format key in string types
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Formats {
+    Date,
+    Time,
+    #[serde(alias = "datetime")]
+    DateTime,
+    Email,
+    Hostname,
+    Ipv4,
+    Ipv6,
+    Uri,
+    Uuid,
+}
+
+impl Formats {
+    /// every format as written in the schema
+    pub const NAMES: [&'static str; 9] = [
+        "date", "time", "datetime", "email", "hostname", "ipv4", "ipv6", "uri", "uuid",
+    ];
+}
+
+impl std::fmt::Display for Formats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Formats::Date => f.write_str("date"),
+            Formats::Time => f.write_str("time"),
+            Formats::DateTime => f.write_str("date-time"),
+            Formats::Email => f.write_str("email"),
+            Formats::Hostname => f.write_str("hostname"),
+            Formats::Ipv4 => f.write_str("ipv4"),
+            Formats::Ipv6 => f.write_str("ipv6"),
+            Formats::Uri => f.write_str("uri"),
+            Formats::Uuid => f.write_str("uuid"),
+        }
+    }
+}
+
+/// the serde `rename_all` rules, used to normalize the keys instead of keeping them as written
+#[derive(Clone, Copy, Debug)]
+pub enum RenameAll {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl std::fmt::Display for RenameAll {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenameAll::Lower => f.write_str("lowercase"),
+            RenameAll::Upper => f.write_str("UPPERCASE"),
+            RenameAll::Pascal => f.write_str("PascalCase"),
+            RenameAll::Camel => f.write_str("camelCase"),
+            RenameAll::Snake => f.write_str("snake_case"),
+            RenameAll::ScreamingSnake => f.write_str("SCREAMING_SNAKE_CASE"),
+            RenameAll::Kebab => f.write_str("kebab-case"),
+            RenameAll::ScreamingKebab => f.write_str("SCREAMING-KEBAB-CASE"),
+        }
+    }
+}
+
+impl RenameAll {
+    /// renames a field the way serde does
+    pub fn apply(&self, field: &str) -> String {
+        match self {
+            RenameAll::Lower => field.to_lowercase(),
+            RenameAll::Upper => field.to_uppercase(),
+            RenameAll::Pascal => inflections::case::to_pascal_case(field),
+            RenameAll::Camel => inflections::case::to_camel_case(field),
+            RenameAll::Snake => inflections::case::to_snake_case(field),
+            RenameAll::ScreamingSnake => inflections::case::to_constant_case(field),
+            RenameAll::Kebab => inflections::case::to_kebab_case(field),
+            RenameAll::ScreamingKebab => inflections::case::to_kebab_case(field).to_uppercase(),
+        }
+    }
+}
+
+/// contains every ident that's considered as a keyword
+///
+/// ```ignore
+/// schema2struct!{
+///     type: ...,
+///     title: "...",
+///     default: "...",
+///     required: [...],
+///     description: "...",
+///     items: ..., // you can also use { type: ... }
+///     properties: {
+///         "...": {...}
+///     }
+///    
+/// }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub enum JsonSchemaKeywords {
+    Type,
+    Title,
+    Required,
+    Description,
+    Items,
+    Properties,
+    Default,
+    Examples,
+    Enum,
+    Const,
+    MinLength,
+    MaxLenght,
+    Pattern,
+    Format,
+    Minimum,
+    Maximum,
+    MinItems,
+    MaxItems,
+    UniqueItems,
+    Contains,
+    Struct,
+    Derive,
+    Attrs,
+    Vis,
+    Mod,
+    RenameAll,
+    FromExample,
+}
+
+impl JsonSchemaKeywords {
+    /// every keyword as written in the schema, along with the ones handled at parsing
+    pub const NAMES: [&'static str; 27] = [
+        "type",
+        "title",
+        "required",
+        "description",
+        "items",
+        "properties",
+        "default",
+        "examples",
+        "enum",
+        "const",
+        "min_length",
+        "max_length",
+        "pattern",
+        "format",
+        "minimum",
+        "maximum",
+        "max_items",
+        "min_items",
+        "unique_items",
+        "contains",
+        "struct",
+        "derive",
+        "attrs",
+        "vis",
+        "mod",
+        "rename_all",
+        "from_example",
+    ];
+}
+
+/// stores what's after the `:`
+///
+/// ```ignore
+/// schema2struct!{
+///     type: ... // the `...` is the value
+/// }
+///     
+///    
+/// ```
+#[allow(dead_code)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum JsonSchemaValues {
+    // a schema file has no idents, every string in it is a `Str`
+    #[serde(serialize_with = "serialize_ident", skip_deserializing)]
+    Ident(syn::Ident),
+    Str(String),
+    // an integer or a float, like `2` or `0.5`
+    Number(Number),
+    Bool(bool),
+    Char(char),
+    Array(Vec<JsonSchemaValues>),
+}
+
+impl JsonSchemaValues {
+    pub fn get_str(&self) -> Option<&String> {
+        match self {
+            JsonSchemaValues::Str(ref s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+impl JsonSchemaTypes {
+    /// every type as written in the schema
+    pub const NAMES: [&'static str; 6] =
+        ["object", "string", "array", "number", "integer", "boolean"];
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, JsonSchemaTypes::None)
+    }
+}
+
+impl std::fmt::Display for JsonSchemaTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonSchemaTypes::Array => f.write_str("array"),
+            JsonSchemaTypes::Object => f.write_str("object"),
+            JsonSchemaTypes::String => f.write_str("string"),
+            JsonSchemaTypes::Number => f.write_str("number"),
+            JsonSchemaTypes::Integer => f.write_str("integer"),
+            JsonSchemaTypes::Boolean => f.write_str("boolean"),
+            JsonSchemaTypes::None => f.write_str("null"),
+        }
+    }
+}
+
+impl std::fmt::Display for JsonSchemaValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JsonSchemaValues::Ident(ident) => f.write_str(&ident.to_string()),
+            JsonSchemaValues::Str(s) => f.write_str(s),
+            JsonSchemaValues::Number(num) => f.write_str(&format!("{}", num)),
+            JsonSchemaValues::Bool(b) => f.write_str(&format!("{}", b)),
+            JsonSchemaValues::Char(c) => f.write_str(&format!("{}", c)),
+            JsonSchemaValues::Array(array) => f.write_str(&format!("{:?}", array)),
+        }
+    }
+}
+
+impl JsonSchema {
+    /// converts the struct to an empty json
+    ///
+    /// ```rust
+    /// struct Foo {
+    ///     baz: String    
+    /// }
+    ///
+    /// ```
+    ///
+    /// get's turned into
+    ///
+    /// ```json
+    /// {
+    ///     "baz": ""
+    /// }
+    /// ```
+    ///
+    /// it's used at the generation
+    pub fn to_json_sample(&self) -> Value {
+        let mut json = Map::new();
+
+        if !matches!(self.ty, JsonSchemaTypes::Object) {
+            return Self::get_in_type(self);
+        }
+
+        if let Some(struct_name) = self.struct_name.as_ref() {
+            json.insert("struct_name".into(), Value::String(struct_name.to_owned()));
+        }
+
+        if let Some(properties) = self.properties.as_ref() {
+            for (key, property) in properties {
+                json.insert(key.to_owned(), Self::to_json_sample(property));
+            }
+        }
+
+        json.into()
+    }
+
+    /// Whether it's a map, an object with only `additionalProperties`
+    pub fn is_map(&self) -> bool {
+        matches!(self.ty, JsonSchemaTypes::Object)
+            && self.properties.as_ref().is_none_or(IndexMap::is_empty)
+            && self.additional_properties.is_some()
+    }
+
+    fn get_in_type(schema: &JsonSchema) -> Value {
+        // it's an enum, it's generated like a nested object
+        if schema.one_of.is_some() {
+            return Value::Object(Map::new());
+        }
+
+        match schema.ty {
+            JsonSchemaTypes::String => Value::String(String::new()),
+            JsonSchemaTypes::None => Value::Null,
+            JsonSchemaTypes::Number | JsonSchemaTypes::Integer => Value::Number(Number::from(0)),
+            JsonSchemaTypes::Boolean => Value::Bool(false),
+            JsonSchemaTypes::Array => {
+                if let Some(items) = &schema.items {
+                    Value::Array(vec![Self::get_in_type(items)])
+                } else {
+                    Value::Array(Vec::new())
+                }
+            }
+            JsonSchemaTypes::Object => Value::Object(Map::new()),
+        }
+    }
+
+    /// the JSON Schema version every generated document follows
+    pub const DIALECT: &'static str = "https://json-schema.org/draft/2020-12/schema";
+
+    /// converts the schema into a JSON Schema 2020-12 document
+    ///
+    /// it starts with the `$schema` header, and the `title` is the struct name if there's none,
+    /// a `nullable` schema gets the `"null"` type along with it's own
+    ///
+    /// ```json
+    /// {
+    ///     "$schema": "https://json-schema.org/draft/2020-12/schema",
+    ///     "title": "User",
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "name": { "type": ["string", "null"] }
+    ///     }
+    /// }
+    /// ```
+    pub fn to_json_schema(&self) -> Value {
+        let mut schema = serde_json::to_value(self).unwrap_or_default();
+        Self::to_standard_types(&mut schema);
+
+        let Value::Object(schema) = schema else {
+            unreachable!("a schema is always serialized to an object")
+        };
+
+        let mut document = Map::new();
+
+        document.insert("$schema".into(), Self::DIALECT.into());
+
+        if let Some(title) = self.title.as_ref().or(self.struct_name.as_ref()) {
+            document.insert("title".into(), title.as_str().into());
+        }
+
+        document.extend(schema);
+        document.into()
+    }
+
+    // turns the `nullable` flag into a `"null"` type, and drops the type of a schema that accepts anything
+    fn to_standard_types(schema: &mut Value) {
+        let Some(schema) = schema.as_object_mut() else {
+            return;
+        };
+
+        let nullable = schema.shift_remove("nullable") == Some(Value::Bool(true));
+
+        match schema.get("type").and_then(Value::as_str) {
+            Some("none") | None => {
+                schema.shift_remove("type");
+
+                if let Some(Value::Array(one_of)) = schema.get_mut("oneOf").filter(|_| nullable) {
+                    one_of.push(serde_json::json!({ "type": "null" }));
+                }
+            }
+            Some(ty) if nullable => {
+                schema["type"] = Value::Array(vec![ty.into(), "null".into()]);
+            }
+            Some(_) => {}
+        }
+
+        if let Some(Value::Object(properties)) = schema.get_mut("properties") {
+            properties.values_mut().for_each(Self::to_standard_types);
+        }
+
+        for key in ["items", "contains", "additionalProperties"] {
+            if let Some(nested) = schema.get_mut(key) {
+                Self::to_standard_types(nested);
+            }
+        }
+
+        if let Some(Value::Array(one_of)) = schema.get_mut("oneOf") {
+            one_of.iter_mut().for_each(Self::to_standard_types);
+        }
+    }
+}
+
+fn deserialize_additional_properties<'de, D>(
+    deserializer: D,
+) -> Result<Option<Box<JsonSchema>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum AdditionalProperties {
+        // it's only there to be accepted
+        #[allow(dead_code)]
+        Allowed(bool),
+        Schema(Box<JsonSchema>),
+    }
+
+    Ok(match AdditionalProperties::deserialize(deserializer)? {
+        AdditionalProperties::Allowed(_) => None,
+        AdditionalProperties::Schema(schema) => Some(schema),
+    })
+}
+
+// Custom serializer for Ident
+fn serialize_ident<S>(ident: &syn::Ident, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&ident.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::files::parse_schema;
+
+    #[test]
+    fn json_schema_has_the_header_and_title() {
+        let mut schema = parse_schema(json!({
+            "type": "object",
+            "properties": { "name": { "type": "string" } },
+            "required": ["name"]
+        }))
+        .unwrap();
+        schema.struct_name = Some("User".to_owned());
+
+        assert_eq!(
+            schema.to_json_schema(),
+            json!({
+                "$schema": JsonSchema::DIALECT,
+                "title": "User",
+                "type": "object",
+                "properties": { "name": { "type": "string" } },
+                "required": ["name"]
+            })
+        );
+
+        schema.title = Some("A user".to_owned());
+        assert_eq!(schema.to_json_schema()["title"], "A user");
+    }
+
+    #[test]
+    fn nullable_becomes_a_null_type() {
+        let schema = parse_schema(json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": ["string", "null"] },
+                "tags": { "type": "array", "items": { "type": "integer", "nullable": true } },
+                "pet": {
+                    "oneOf": [{ "type": "object" }, { "type": "string" }],
+                    "nullable": true
+                },
+                "anything": {}
+            }
+        }))
+        .unwrap();
+
+        let document = schema.to_json_schema();
+        let properties = &document["properties"];
+
+        assert_eq!(properties["name"], json!({ "type": ["string", "null"] }));
+        assert_eq!(
+            properties["tags"]["items"],
+            json!({ "type": ["integer", "null"] })
+        );
+        assert_eq!(properties["pet"]["oneOf"][2], json!({ "type": "null" }));
+        assert_eq!(properties["anything"], json!({}));
+    }
+
+    #[test]
+    fn number_bounds_are_kept() {
+        let schema = parse_schema(json!({
+            "type": "object",
+            "properties": {
+                "ratio": { "type": "number", "minimum": 0.5, "maximum": 2 },
+                "version": { "const": 2.0 }
+            }
+        }))
+        .unwrap();
+
+        let document = schema.to_json_schema();
+        let properties = &document["properties"];
+
+        assert_eq!(properties["ratio"]["minimum"], 0.5);
+        assert_eq!(properties["ratio"]["maximum"], 2);
+        assert_eq!(properties["version"]["const"], 2.0);
+    }
+}
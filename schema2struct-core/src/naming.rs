@@ -0,0 +1,288 @@
+/// Naming module for the generated items
+///
+/// Schema keys can be anything, like `"type"`, `"2fa_enabled"`, `"$id"` or `"@context"`,
+/// this module turns them into valid Rust identifiers
+///
+/// # Rules
+/// - Characters that can't be in an identifier are dropped
+/// - Rust keywords become raw identifiers (`r#type`), or get a `_` suffix if they can't be raw (`self_`)
+/// - Names that can't start an identifier, like the ones starting with a digit, get a `_` prefix
+///
+/// It also keeps track of every generated type name, so two types never end up with the same name
+use std::collections::HashMap;
+
+use inflections::Inflect;
+use proc_macro2::{Span, TokenStream};
+use syn::Ident;
+use unicode_ident::{is_xid_continue, is_xid_start};
+
+/// keywords that can't be written as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 5] = ["self", "Self", "super", "crate", "_"];
+
+/// every strict and reserved keyword, along with the edition specific ones
+const KEYWORDS: [&str; 52] = [
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "typeof", "unsized", "virtual", "yield", "try", "gen",
+];
+
+/// A generated identifier along with whether it had to be changed from the schema key
+pub struct SanitizedIdent {
+    pub ident: Ident,
+
+    /// `true` if the key lost more than it's casing, so serde can't get back to it by itself
+    pub changed: bool,
+}
+
+/// Turns a schema key into a field identifier
+///
+/// `"user_ID"` becomes `user_id`, `"type"` becomes `r#type` and `"2fa_enabled"` becomes `_2fa_enabled`
+pub fn field_ident(key: &str) -> SanitizedIdent {
+    let name = strip_invalid_chars(key).to_snake_case();
+
+    // an empty key like `""` has nothing to keep, it's still renamed back to it
+    let name = if name.is_empty() {
+        "field".to_owned()
+    } else {
+        name
+    };
+
+    let changed = name != key.to_snake_case();
+
+    to_ident(name, changed)
+}
+
+/// Turns a schema key into a type name, with an optional prefix
+///
+/// `"@context"` becomes `Context`, and with the `User` prefix it becomes `UserContext`
+pub fn type_ident(prefix: Option<&Ident>, key: &str) -> Ident {
+    let name = strip_invalid_chars(key).to_pascal_case();
+    let name = match prefix {
+        Some(prefix) => format!("{}{}", prefix, name),
+        None if name.is_empty() => "Type".to_owned(),
+        None => name,
+    };
+
+    to_ident(name, false).ident
+}
+
+/// where a generated type came from
+struct GeneratedType {
+    /// the property path, like `User.address`
+    path: String,
+
+    /// the generated code, `None` for the types that are still being generated
+    tokens: Option<String>,
+}
+
+/// Tracks every generated type name in a single macro call
+#[derive(Default)]
+pub struct TypeNames {
+    types: HashMap<String, GeneratedType>,
+}
+
+impl TypeNames {
+    /// Takes a name before it's type gets generated, so any other type using it is a conflict
+    pub fn reserve(&mut self, name: &Ident, path: &str) {
+        self.types.insert(
+            name.to_string(),
+            GeneratedType {
+                path: path.to_owned(),
+                tokens: None,
+            },
+        );
+    }
+
+    /// Registers a generated type under it's name
+    ///
+    /// # Returns
+    /// `true` if the type should be emitted, `false` if the exact same type was already registered
+    ///
+    /// # Errors
+    /// Emits an error if a different type already has the name
+    pub fn register(&mut self, name: &Ident, path: &str, tokens: &TokenStream, span: Span) -> bool {
+        let tokens = tokens.to_string();
+
+        let Some(existing) = self.types.get(&name.to_string()) else {
+            self.types.insert(
+                name.to_string(),
+                GeneratedType {
+                    path: path.to_owned(),
+                    tokens: Some(tokens),
+                },
+            );
+
+            return true;
+        };
+
+        // the same schema under the same name is the same type, it only needs to be emitted once
+        if existing.tokens.as_ref() == Some(&tokens) {
+            return false;
+        }
+
+        emit_error!(
+            span,
+            "both `{}` and `{}` generate the type `{}` from different schemas, consider giving one of them a different `struct` name",
+            existing.path,
+            path,
+            name
+        );
+
+        false
+    }
+}
+
+// replaces everything that can't be in an identifier with a space, so the casing treats it as a separator,
+// a run of them is a single space, the casing doesn't take two in a row
+fn strip_invalid_chars(key: &str) -> String {
+    key.split(|c: char| !is_xid_continue(c))
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn to_ident(name: String, changed: bool) -> SanitizedIdent {
+    if !name.starts_with(|c: char| is_xid_start(c) || c == '_') {
+        return SanitizedIdent {
+            ident: Ident::new(&format!("_{}", name), Span::call_site()),
+            changed: true,
+        };
+    }
+
+    if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+        return SanitizedIdent {
+            ident: Ident::new(&format!("{}_", name), Span::call_site()),
+            changed: true,
+        };
+    }
+
+    if KEYWORDS.contains(&name.as_str()) {
+        return SanitizedIdent {
+            ident: Ident::new_raw(&name, Span::call_site()),
+            changed,
+        };
+    }
+
+    SanitizedIdent {
+        ident: Ident::new(&name, Span::call_site()),
+        changed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::*;
+
+    fn field(key: &str) -> (String, bool) {
+        let sanitized = field_ident(key);
+        (sanitized.ident.to_string(), sanitized.changed)
+    }
+
+    #[test]
+    fn field_idents() {
+        assert_eq!(field("name"), ("name".to_owned(), false));
+        assert_eq!(field("userId"), ("user_id".to_owned(), false));
+        assert_eq!(field("type"), ("r#type".to_owned(), false));
+        assert_eq!(field("self"), ("self_".to_owned(), true));
+        assert_eq!(field("2fa_enabled"), ("_2fa_enabled".to_owned(), true));
+        assert_eq!(field("$id"), ("id".to_owned(), true));
+        assert_eq!(field("@context"), ("context".to_owned(), true));
+        assert_eq!(field("user  name"), ("user_name".to_owned(), true));
+    }
+
+    #[test]
+    fn empty_field_is_renamed_back() {
+        assert_eq!(field(""), ("field".to_owned(), true));
+        assert_eq!(field("$"), ("field".to_owned(), true));
+    }
+
+    #[test]
+    fn type_idents() {
+        let user = Ident::new("User", Span::call_site());
+
+        assert_eq!(type_ident(None, "address").to_string(), "Address");
+        assert_eq!(type_ident(None, "@context").to_string(), "Context");
+        assert_eq!(
+            type_ident(Some(&user), "@context").to_string(),
+            "UserContext"
+        );
+        assert_eq!(
+            type_ident(Some(&user), "home_address").to_string(),
+            "UserHomeAddress"
+        );
+        assert_eq!(type_ident(None, "").to_string(), "Type");
+        assert_eq!(type_ident(None, "2fa").to_string(), "_2fa");
+        assert_eq!(
+            type_ident(None, "delete /items/{id}").to_string(),
+            "DeleteItemsId"
+        );
+    }
+
+    #[test]
+    fn same_type_is_emitted_once() {
+        let name = Ident::new("Address", Span::call_site());
+        let tokens = quote! { struct Address; };
+
+        let mut names = TypeNames::default();
+
+        let result = crate::diagnostics::collect(|| {
+            Ok((
+                names.register(&name, "User.address", &tokens, Span::call_site()),
+                names.register(&name, "Order.address", &tokens, Span::call_site()),
+            ))
+        });
+
+        assert_eq!(result.unwrap(), (true, false));
+    }
+
+    #[test]
+    fn different_types_with_one_name_conflict() {
+        let name = Ident::new("Address", Span::call_site());
+
+        let mut names = TypeNames::default();
+
+        let result = crate::diagnostics::collect(|| {
+            names.register(
+                &name,
+                "User.address",
+                &quote! { struct Address; },
+                Span::call_site(),
+            );
+            names.register(
+                &name,
+                "Order.address",
+                &quote! { struct Address(u8); },
+                Span::call_site(),
+            );
+            Ok(())
+        });
+
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "both `User.address` and `Order.address` generate the type `Address` from different schemas, consider giving one of them a different `struct` name"
+        );
+    }
+
+    #[test]
+    fn reserved_name_conflicts() {
+        let name = Ident::new("UserAddress", Span::call_site());
+
+        let mut names = TypeNames::default();
+        names.reserve(&name, "User");
+
+        let result = crate::diagnostics::collect(|| {
+            Ok(names.register(
+                &name,
+                "User.address",
+                &quote! { struct UserAddress; },
+                Span::call_site(),
+            ))
+        });
+
+        assert!(result.is_err());
+    }
+}
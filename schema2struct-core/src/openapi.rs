@@ -0,0 +1,457 @@
+/// OpenAPI module
+///
+/// Imports the component schemas of an OpenAPI 3.0 or 3.1 document, so they go through the same
+/// generator as the macro and the schema files, and the types of it's operations with [`operations`]
+///
+/// # Dialect
+/// - `$ref`s to `#/components/schemas/...` are resolved, a referenced object keeps the name of it's
+///   component, so it's generated once however many times it's referenced
+/// - `nullable: true` of 3.0 and the `["string", "null"]` types of 3.1 both become an `Option`
+/// - A property is an `Option` unless it's `required`
+/// - A `oneOf` becomes an enum, tagged by the property of it's `discriminator` if it has one
+/// - The objects of an `allOf` are merged into a single one, like a component extending another
+/// - The keywords the model can't hold, like an `int64` format, are left out
+mod operations;
+
+pub use operations::{generate_operations, Body, Operation, Response, Status};
+
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use serde_json::Value;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned as _,
+};
+
+use crate::{
+    checkers::closest_match,
+    diagnostics,
+    files::{
+        manifest_path, parse_input, parse_schema, read_document, rebuilt_on_change, string_value,
+        Definitions, FileError, FileErrorKind, InputOptions,
+    },
+    generator::Options,
+    models::{JsonSchema, JsonSchemaTypes},
+    naming::type_ident,
+};
+
+/// where the component schemas are in the document, and what their references start with
+const COMPONENTS_POINTER: &str = "/components/schemas";
+const COMPONENTS_REF: &str = "#/components/schemas/";
+
+/// An OpenAPI document, with it's component schemas ready to be resolved
+pub struct OpenApiDocument {
+    path: PathBuf,
+    document: Value,
+    components: IndexMap<String, JsonSchema>,
+}
+
+impl OpenApiDocument {
+    /// Reads an OpenAPI document, written in JSON, YAML or TOML by it's extension
+    ///
+    /// # Errors
+    /// - The file can't be read or parsed
+    /// - It's not an OpenAPI 3.x document
+    /// - A component schema isn't valid
+    pub fn read(path: &Path) -> Result<Self, FileError> {
+        let document = read_document(path)?;
+
+        let invalid = |location: &str, message: String| FileError {
+            path: path.to_owned(),
+            kind: FileErrorKind::Invalid {
+                location: location.to_owned(),
+                message,
+            },
+        };
+
+        match document.get("openapi").and_then(Value::as_str) {
+            Some(version) if version.starts_with("3.") => {}
+            Some(version) => {
+                return Err(invalid(
+                    "openapi",
+                    format!(
+                        "OpenAPI {} isn't supported, only 3.0 and 3.1 documents are",
+                        version
+                    ),
+                ))
+            }
+            None => {
+                return Err(invalid(
+                    "openapi",
+                    "it's not an OpenAPI document, there's no `openapi` version".to_owned(),
+                ))
+            }
+        }
+
+        let schemas = match document.pointer(COMPONENTS_POINTER) {
+            Some(Value::Object(schemas)) => schemas.clone(),
+            Some(_) => {
+                return Err(invalid(
+                    "components.schemas",
+                    "the component schemas must be an object".to_owned(),
+                ))
+            }
+            None => Default::default(),
+        };
+
+        let mut components = IndexMap::new();
+
+        for (name, schema) in schemas {
+            let schema = parse_schema(schema)
+                .map_err(|e| invalid(&format!("components.schemas.{}", name), e.to_string()))?;
+
+            components.insert(name, schema);
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            document,
+            components,
+        })
+    }
+
+    /// The names of every component schema, in the order they're declared
+    pub fn component_names(&self) -> impl Iterator<Item = &str> {
+        self.components.keys().map(String::as_str)
+    }
+
+    /// Gets a component schema with every reference in it resolved, ready to be generated
+    ///
+    /// # Errors
+    /// - There's no such component
+    /// - A reference can't be resolved, or it's recursive
+    /// - It's not an object or a `oneOf`, so it can't become a type
+    pub fn component(&self, name: &str) -> Result<JsonSchema, FileError> {
+        let Some(component) = self.components.get(name) else {
+            let names = self.component_names().collect::<Vec<_>>();
+
+            let message = match closest_match(name, &names) {
+                Some(suggestion) => format!(
+                    "there's no `{}` component, did you mean `{}`?",
+                    name, suggestion
+                ),
+                None => format!("there's no `{}` component", name),
+            };
+
+            return Err(self.invalid("components.schemas", message));
+        };
+
+        let location = format!("components.schemas.{}", name);
+
+        let mut schema = component.clone();
+        self.resolve(&mut schema, &mut vec![name.to_owned()])
+            .map_err(|message| self.invalid(&location, message))?;
+
+        if !matches!(schema.ty, JsonSchemaTypes::Object) && schema.one_of.is_none() {
+            return Err(self.invalid(
+                &location,
+                "only an object or a `oneOf` component can become a type",
+            ));
+        }
+
+        schema.struct_name = Some(type_ident(None, name).to_string());
+        schema.depth = 1;
+
+        Ok(schema)
+    }
+
+    // a schema from anywhere in the document, with every reference in it resolved
+    fn schema_at(&self, schema: &Value, location: &str) -> Result<JsonSchema, FileError> {
+        let mut schema =
+            parse_schema(schema.clone()).map_err(|e| self.invalid(location, e.to_string()))?;
+
+        self.resolve(&mut schema, &mut Vec::new())
+            .map_err(|message| self.invalid(location, message))?;
+
+        Ok(schema)
+    }
+
+    fn invalid(&self, location: &str, message: impl Into<String>) -> FileError {
+        FileError {
+            path: self.path.clone(),
+            kind: FileErrorKind::Invalid {
+                location: location.to_owned(),
+                message: message.into(),
+            },
+        }
+    }
+
+    // replaces every reference with the component it points to, `stack` is the components being
+    // resolved
+    fn resolve(&self, schema: &mut JsonSchema, stack: &mut Vec<String>) -> Result<(), String> {
+        Definitions {
+            schemas: &self.components,
+            prefixes: &[COMPONENTS_REF],
+            place: "the components",
+        }
+        .resolve(schema, stack)
+    }
+}
+
+/// The input of `schema2struct!(openapi = "api.yaml", components = ["User"], operations = ["getUser"])`
+///
+/// every component is generated if there's neither `components` nor `operations`
+pub struct OpenApiInput {
+    pub path: syn::LitStr,
+    pub components: Option<Vec<syn::LitStr>>,
+    pub operations: Option<Vec<syn::LitStr>>,
+    pub options: InputOptions,
+}
+
+impl Parse for OpenApiInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut path = None;
+        let mut components = None;
+        let mut operations = None;
+
+        let options = parse_input(
+            input,
+            &["openapi", "components", "operations"],
+            |key, input| {
+                match key.to_string().as_str() {
+                    "openapi" => {
+                        path = Some(string_value(
+                            input,
+                            "`openapi` is the path of the document, like `\"api.yaml\"`",
+                        )?)
+                    }
+                    "components" => {
+                        components = Some(string_array(
+                            input,
+                            "a component is a string, like `\"User\"`",
+                        )?)
+                    }
+                    _ => {
+                        operations = Some(string_array(
+                            input,
+                            "an operation is it's `operationId`, like `\"getUser\"`",
+                        )?)
+                    }
+                }
+
+                Ok(())
+            },
+        )?;
+
+        let Some(path) = path else {
+            return Err(input.error("`openapi = \"...\"` is required"));
+        };
+
+        Ok(Self {
+            path,
+            components,
+            operations,
+            options,
+        })
+    }
+}
+
+fn string_array(input: ParseStream, message: &str) -> syn::Result<Vec<syn::LitStr>> {
+    let syn::Expr::Array(array) = input.parse::<syn::Expr>()? else {
+        return Err(input.error("expected an array, like `[...]`"));
+    };
+
+    array
+        .elems
+        .into_iter()
+        .map(|element| match element {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Ok(lit),
+            other => Err(syn::Error::new(other.span(), message)),
+        })
+        .collect()
+}
+
+/// Whether the macro input is an OpenAPI import, it starts with `openapi`
+pub fn is_openapi_input(tokens: &TokenStream) -> bool {
+    matches!(
+        tokens.clone().into_iter().next(),
+        Some(proc_macro2::TokenTree::Ident(ident)) if ident == "openapi"
+    )
+}
+
+/// Generates the types of the components and the operations of an OpenAPI document, the path is
+/// relative to the crate's `Cargo.toml`
+///
+/// # Errors
+/// - The input is invalid
+/// - The document can't be read, or a component or an operation can't be generated
+pub fn expand_openapi(tokens: TokenStream, options: &Options) -> syn::Result<TokenStream> {
+    let input = syn::parse2::<OpenApiInput>(tokens)?;
+
+    let path = manifest_path(&input.path);
+
+    let document =
+        OpenApiDocument::read(&path).map_err(|e| syn::Error::new(input.path.span(), e))?;
+
+    // the operations only bring the components they use
+    let components = match (&input.components, &input.operations) {
+        (Some(components), _) => components
+            .iter()
+            .map(|component| (component.value(), component.span()))
+            .collect(),
+        (None, Some(_)) => Vec::new(),
+        (None, None) => document
+            .component_names()
+            .map(|name| (name.to_owned(), input.path.span()))
+            .collect::<Vec<_>>(),
+    };
+
+    let (schemas, operations) = diagnostics::collect(|| {
+        let schemas = components
+            .iter()
+            .filter_map(|(name, span)| match document.component(name) {
+                Ok(schema) => Some(schema),
+                Err(e) => {
+                    emit_error!(*span, e.to_string());
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let operations = input
+            .operations
+            .iter()
+            .flatten()
+            .filter_map(|id| match document.operation(&id.value()) {
+                Ok(operation) => Some(operation),
+                Err(e) => {
+                    emit_error!(id.span(), e.to_string());
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok((schemas, operations))
+    })?;
+
+    let output = generate_operations(schemas, &operations, &input.options.apply(options))?;
+
+    Ok(rebuilt_on_change(&path, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::tests::temp_file;
+    use crate::models::JsonSchemaValues;
+
+    const DOCUMENT: &str = r##"
+openapi: 3.0.3
+info: { title: Pets, version: "1" }
+paths: {}
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Cat"
+        - $ref: "#/components/schemas/Dog"
+      discriminator:
+        propertyName: kind
+        mapping:
+          cat: "#/components/schemas/Cat"
+    Cat:
+      type: object
+      properties:
+        lives: { type: integer, format: int32 }
+    Dog:
+      allOf:
+        - $ref: "#/components/schemas/Named"
+        - type: object
+          properties:
+            good: { type: boolean }
+    Named:
+      type: object
+      properties:
+        name: { type: string, nullable: true }
+      required: [name]
+    Owner:
+      type: object
+      properties:
+        pet: { $ref: "#/components/schemas/Pet" }
+        friend: { $ref: "#/components/schemas/Owner" }
+    Name:
+      type: string
+"##;
+
+    // every test reads it's own copy, they run in parallel
+    fn document(name: &str) -> OpenApiDocument {
+        OpenApiDocument::read(&temp_file(name, DOCUMENT)).unwrap()
+    }
+
+    fn message(error: FileError) -> String {
+        match error.kind {
+            FileErrorKind::Invalid { message, .. } => message,
+            kind => panic!("expected an invalid document, got {:?}", kind),
+        }
+    }
+
+    #[test]
+    fn discriminated_variants_are_tagged() {
+        let pet = document("pet.yaml").component("Pet").unwrap();
+        let variants = pet.one_of.unwrap();
+
+        let tags = variants
+            .iter()
+            .map(|variant| {
+                let tag = &variant.properties.as_ref().unwrap()["kind"];
+                (
+                    variant.struct_name.clone().unwrap(),
+                    tag.const_value.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert!(matches!(
+            &tags[..],
+            [
+                (cat, Some(JsonSchemaValues::Str(cat_tag))),
+                (dog, Some(JsonSchemaValues::Str(dog_tag))),
+            ] if cat == "Cat" && cat_tag == "cat" && dog == "Dog" && dog_tag == "Dog"
+        ));
+    }
+
+    #[test]
+    fn all_of_component_is_merged() {
+        let dog = document("dog.yaml").component("Dog").unwrap();
+        let properties = dog.properties.as_ref().unwrap();
+
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["name", "good"]);
+        assert_eq!(properties["name"].nullable, Some(true));
+        // `good` isn't `required`, so it's optional
+        assert_eq!(dog.required, Some(vec!["name".to_owned()]));
+        assert_eq!(dog.struct_name.as_deref(), Some("Dog"));
+    }
+
+    #[test]
+    fn component_errors() {
+        let document = document("owner.yaml");
+
+        assert_eq!(
+            message(document.component("Owner").unwrap_err()),
+            "`Owner` references itself through `Owner`, recursive types aren't supported"
+        );
+        assert_eq!(
+            message(document.component("Name").unwrap_err()),
+            "only an object or a `oneOf` component can become a type"
+        );
+        assert_eq!(
+            message(document.component("Pets").unwrap_err()),
+            "there's no `Pets` component, did you mean `Pet`?"
+        );
+    }
+
+    #[test]
+    fn only_openapi_3_is_read() {
+        let path = temp_file("swagger.yaml", "swagger: \"2.0\"\n");
+
+        assert_eq!(
+            message(OpenApiDocument::read(&path).err().unwrap()),
+            "it's not an OpenAPI document, there's no `openapi` version"
+        );
+    }
+}
@@ -0,0 +1,832 @@
+/// Operations module
+///
+/// Builds the types of the operations of an OpenAPI document: a struct for it's path parameters,
+/// one for it's query parameters, the type of it's request body and an enum over it's responses,
+/// all tied together by an implementation of `schema2struct::Operation`
+///
+/// # Naming
+/// Every type is named after the `operationId`, or after the method and the path if there's none,
+/// so `getUser` gets `GetUserPathParams`, `GetUserQueryParams`, `GetUserRequest` and
+/// `GetUserResponse`, and `GetUser` is the operation itself
+use indexmap::IndexMap;
+use proc_macro2::{Literal, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use syn::Ident;
+
+use super::OpenApiDocument;
+use crate::{
+    checkers::closest_match,
+    files::FileError,
+    generator::{extra_derives, generate_all, mapped_type, Options},
+    models::{JsonSchema, JsonSchemaTypes},
+    naming::type_ident,
+};
+
+/// the methods a path item can have an operation for
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// An operation of the document, with every schema in it resolved
+pub struct Operation {
+    /// what every type of the operation starts with, like `GetUser`
+    pub name: Ident,
+
+    /// the uppercase method, like `GET`
+    pub method: String,
+
+    /// the path template, like `/users/{id}`
+    pub path: String,
+
+    /// the object of the path parameters, `None` if there's none
+    pub path_params: Option<JsonSchema>,
+
+    /// the object of the query parameters, `None` if there's none
+    pub query_params: Option<JsonSchema>,
+
+    /// the schema of the JSON request body, `None` if there's none
+    pub request_body: Option<JsonSchema>,
+
+    /// whether the request body has to be sent, it's an `Option` otherwise, like the `required`
+    /// of the document that's `false` by default
+    pub request_body_required: bool,
+
+    pub responses: Vec<Response>,
+}
+
+/// A response of an operation
+pub struct Response {
+    pub status: Status,
+    pub description: Option<String>,
+
+    /// `None` if the response has no body
+    pub body: Option<Body>,
+}
+
+/// The body of a response
+pub enum Body {
+    /// a JSON body with it's schema
+    Json(Box<JsonSchema>),
+
+    /// any other media type, it's kept as bytes
+    Raw,
+}
+
+/// The status a response is declared for
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Status {
+    /// an exact status, like `200`
+    Code(u16),
+
+    /// a range, like `2XX`, with it's first digit
+    Range(u16),
+
+    /// every status that isn't declared
+    Default,
+}
+
+impl Status {
+    fn parse(status: &str) -> Option<Self> {
+        match status.as_bytes() {
+            b"default" => Some(Self::Default),
+            [digit @ b'1'..=b'5', b'X' | b'x', b'X' | b'x'] => {
+                Some(Self::Range(u16::from(digit - b'0')))
+            }
+            _ => status
+                .parse()
+                .ok()
+                .filter(|code| (100..600).contains(code))
+                .map(Self::Code),
+        }
+    }
+
+    // the name of it's variant in the response enum
+    fn variant(self) -> Ident {
+        let name = match self {
+            Self::Code(200) => "Ok",
+            Self::Code(201) => "Created",
+            Self::Code(202) => "Accepted",
+            Self::Code(204) => "NoContent",
+            Self::Code(301) => "MovedPermanently",
+            Self::Code(302) => "Found",
+            Self::Code(304) => "NotModified",
+            Self::Code(400) => "BadRequest",
+            Self::Code(401) => "Unauthorized",
+            Self::Code(403) => "Forbidden",
+            Self::Code(404) => "NotFound",
+            Self::Code(409) => "Conflict",
+            Self::Code(422) => "UnprocessableEntity",
+            Self::Code(429) => "TooManyRequests",
+            Self::Code(500) => "InternalServerError",
+            Self::Code(503) => "ServiceUnavailable",
+            Self::Code(code) => return format_ident!("Status{}", code),
+            Self::Range(digit) => return format_ident!("Status{}XX", digit),
+            Self::Default => "Default",
+        };
+
+        format_ident!("{}", name)
+    }
+}
+
+impl OpenApiDocument {
+    /// The id of every operation, in the order they're declared
+    ///
+    /// an operation without an `operationId` goes by it's method and path, like `GET /users/{id}`
+    pub fn operation_ids(&self) -> Vec<String> {
+        self.operations()
+            .map(|(path, method, operation)| operation_id(path, method, operation))
+            .collect()
+    }
+
+    /// Gets an operation by it's id, with every schema in it resolved, ready to be generated
+    ///
+    /// # Errors
+    /// - There's no such operation
+    /// - A reference can't be resolved, or it's recursive
+    /// - A parameter, the request body or a response isn't valid
+    pub fn operation(&self, id: &str) -> Result<Operation, FileError> {
+        let Some((path, method, operation)) = self
+            .operations()
+            .find(|(path, method, operation)| operation_id(path, method, operation) == id)
+        else {
+            let ids = self.operation_ids();
+            let ids = ids.iter().map(String::as_str).collect::<Vec<_>>();
+
+            let message = match closest_match(id, &ids) {
+                Some(suggestion) => format!(
+                    "there's no `{}` operation, did you mean `{}`?",
+                    id, suggestion
+                ),
+                None => format!("there's no `{}` operation", id),
+            };
+
+            return Err(self.invalid("paths", message));
+        };
+
+        let location = format!("paths.{}.{}", path, method);
+
+        let name = match operation.get("operationId").and_then(Value::as_str) {
+            Some(id) => type_ident(None, id),
+            None => type_ident(None, &format!("{} {}", method, path)),
+        };
+
+        // the parameters of the path item apply to every operation in it, unless it overrides them
+        let mut parameters = IndexMap::new();
+
+        let path_item = &self.document["paths"][path];
+        let declared = [&path_item["parameters"], &operation["parameters"]];
+
+        for (i, parameter) in declared
+            .into_iter()
+            .filter_map(Value::as_array)
+            .flatten()
+            .enumerate()
+        {
+            let parameter_location = format!("{}.parameters[{}]", location, i);
+            let parameter = self.deref(parameter, &parameter_location)?;
+
+            let (Some(name), Some(place)) = (
+                parameter.get("name").and_then(Value::as_str),
+                parameter.get("in").and_then(Value::as_str),
+            ) else {
+                return Err(self.invalid(
+                    &parameter_location,
+                    "a parameter needs a `name` and an `in`",
+                ));
+            };
+
+            parameters.insert((place.to_owned(), name.to_owned()), parameter);
+        }
+
+        let path_params = self.parameters_object(
+            &parameters,
+            "path",
+            format!("{}PathParams", name),
+            &location,
+        )?;
+        let query_params = self.parameters_object(
+            &parameters,
+            "query",
+            format!("{}QueryParams", name),
+            &location,
+        )?;
+
+        let (request_body, request_body_required) = match operation.get("requestBody") {
+            Some(body) => {
+                let body_location = format!("{}.requestBody", location);
+                let body = self.deref(body, &body_location)?;
+
+                (
+                    self.json_content(body, &body_location)?.transpose()?,
+                    body.get("required") == Some(&Value::Bool(true)),
+                )
+            }
+            None => (None, false),
+        };
+
+        let mut responses = Vec::new();
+
+        for (status, response) in operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            let response_location = format!("{}.responses.{}", location, status);
+
+            let Some(status) = Status::parse(status) else {
+                return Err(self.invalid(
+                    &response_location,
+                    "a response is a status like `200`, a range like `2XX` or `default`",
+                ));
+            };
+
+            let response = self.deref(response, &response_location)?;
+
+            let body = match self.json_content(response, &response_location)? {
+                Some(schema) => Some(Body::Json(Box::new(schema?))),
+                None if response.get("content").is_some() => Some(Body::Raw),
+                None => None,
+            };
+
+            responses.push(Response {
+                status,
+                description: description(response),
+                body,
+            });
+        }
+
+        // the exact statuses are matched before the ranges, and the ranges before `default`
+        responses.sort_by_key(|response| response.status);
+
+        Ok(Operation {
+            name,
+            method: method.to_uppercase(),
+            path: path.to_owned(),
+            path_params,
+            query_params,
+            request_body,
+            request_body_required,
+            responses,
+        })
+    }
+
+    // every operation as it's path, method and declaration
+    fn operations(&self) -> impl Iterator<Item = (&str, &str, &Value)> {
+        self.document
+            .get("paths")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .flat_map(|(path, item)| {
+                METHODS.into_iter().filter_map(move |method| {
+                    item.get(method)
+                        .map(|operation| (path.as_str(), method, operation))
+                })
+            })
+    }
+
+    // follows the `$ref` of a parameter, a request body or a response to it's declaration
+    fn deref<'a>(&'a self, mut value: &'a Value, location: &str) -> Result<&'a Value, FileError> {
+        let mut seen = Vec::new();
+
+        while let Some(reference) = value.get("$ref").and_then(Value::as_str) {
+            if seen.contains(&reference) {
+                return Err(self.invalid(location, format!("`{}` references itself", reference)));
+            }
+
+            seen.push(reference);
+
+            value = reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.document.pointer(pointer))
+                .ok_or_else(|| {
+                    self.invalid(location, format!("`{}` can't be resolved", reference))
+                })?;
+        }
+
+        Ok(value)
+    }
+
+    // the object of the parameters in `place`, `None` if there's none
+    fn parameters_object(
+        &self,
+        parameters: &IndexMap<(String, String), &Value>,
+        place: &str,
+        struct_name: String,
+        location: &str,
+    ) -> Result<Option<JsonSchema>, FileError> {
+        let mut properties = IndexMap::new();
+        let mut required = Vec::new();
+
+        for ((_, name), parameter) in parameters.iter().filter(|((p, _), _)| p == place) {
+            let parameter_location = format!("{}.parameters.{}", location, name);
+
+            let mut schema = match parameter.get("schema") {
+                Some(schema) => self.schema_at(schema, &parameter_location)?,
+                None => JsonSchema::default(),
+            };
+
+            schema.description = schema.description.or_else(|| description(parameter));
+
+            // a path parameter is always required
+            if place == "path" || parameter.get("required") == Some(&Value::Bool(true)) {
+                required.push(name.clone());
+            }
+
+            properties.insert(name.clone(), schema);
+        }
+
+        if properties.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(JsonSchema {
+            ty: JsonSchemaTypes::Object,
+            properties: Some(properties),
+            required: Some(required),
+            struct_name: Some(struct_name),
+            depth: 1,
+            ..Default::default()
+        }))
+    }
+
+    // the schema of the JSON content of a request body or a response, `None` if it has no JSON
+    // content, the outer `Result` is for the errors of the content and the inner for the schema
+    #[allow(clippy::type_complexity)]
+    fn json_content(
+        &self,
+        declaration: &Value,
+        location: &str,
+    ) -> Result<Option<Result<JsonSchema, FileError>>, FileError> {
+        let Some(content) = declaration.get("content") else {
+            return Ok(None);
+        };
+
+        let Some(content) = content.as_object() else {
+            return Err(self.invalid(location, "the `content` must be an object"));
+        };
+
+        let json = content.iter().find(|(media_type, _)| {
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+
+            media_type == "application/json" || media_type.ends_with("+json")
+        });
+
+        Ok(json.map(|(media_type, media)| {
+            let location = format!("{}.content.{}", location, media_type);
+
+            match media.get("schema") {
+                Some(schema) => self.schema_at(schema, &location),
+                None => Ok(JsonSchema::default()),
+            }
+        }))
+    }
+}
+
+fn operation_id(path: &str, method: &str, operation: &Value) -> String {
+    match operation.get("operationId").and_then(Value::as_str) {
+        Some(id) => id.to_owned(),
+        None => format!("{} {}", method.to_uppercase(), path),
+    }
+}
+
+fn description(declaration: &Value) -> Option<String> {
+    declaration
+        .get("description")
+        .and_then(Value::as_str)
+        .map(ToOwned::to_owned)
+}
+
+/// Generates the types of some components and operations together, so they share the types
+/// they both use
+///
+/// # Arguments
+/// - `components`: The resolved component schemas, from [`OpenApiDocument::component`]
+/// - `operations`: The operations, from [`OpenApiDocument::operation`]
+/// - `options`: The options of every generated type
+///
+/// # Errors
+/// - Two different types end up with the same name
+pub fn generate_operations(
+    components: Vec<JsonSchema>,
+    operations: &[Operation],
+    options: &Options,
+) -> syn::Result<TokenStream> {
+    let mut roots = components;
+    let mut items = TokenStream::new();
+
+    let vis = &options.vis;
+    let attrs = &options.attrs;
+
+    for operation in operations {
+        let name = &operation.name;
+
+        roots.extend(operation.path_params.iter().cloned());
+        roots.extend(operation.query_params.iter().cloned());
+
+        let unit = || quote!(());
+
+        let path_params = operation.path_params.as_ref().map_or_else(unit, |_| {
+            let ident = format_ident!("{}PathParams", name);
+            quote!(#ident)
+        });
+        let query_params = operation.query_params.as_ref().map_or_else(unit, |_| {
+            let ident = format_ident!("{}QueryParams", name);
+            quote!(#ident)
+        });
+
+        let request = match &operation.request_body {
+            Some(body) => {
+                let request = format_ident!("{}Request", name);
+                let ty = body_type(body, &request, &mut roots, options);
+
+                // a request body that isn't an object still gets the name, as an alias
+                if request != ty.to_string() {
+                    items.extend(quote!(#vis type #request = #ty;));
+                }
+
+                match operation.request_body_required {
+                    true => quote!(#request),
+                    false => quote!(::std::option::Option<#request>),
+                }
+            }
+            None => unit(),
+        };
+
+        let response = format_ident!("{}Response", name);
+        items.extend(response_enum(operation, &response, &mut roots, options));
+
+        let method = &operation.method;
+        let path = &operation.path;
+        let doc = format!("`{} {}`", method, path);
+
+        items.extend(quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #[derive(::std::clone::Clone, ::std::marker::Copy, ::std::fmt::Debug, ::std::default::Default)]
+            #vis struct #name;
+
+            impl ::schema2struct::Operation for #name {
+                const METHOD: &'static str = #method;
+                const PATH: &'static str = #path;
+
+                type PathParams = #path_params;
+                type QueryParams = #query_params;
+                type Request = #request;
+                type Response = #response;
+
+                fn decode_response(
+                    status: u16,
+                    body: &[u8],
+                ) -> ::std::result::Result<Self::Response, ::schema2struct::ResponseError> {
+                    #response::from_response(status, body)
+                }
+            }
+        });
+    }
+
+    let output = generate_all(&roots, options)?;
+
+    Ok(quote! {
+        #output
+        #items
+    })
+}
+
+// the enum over the responses of an operation, with a variant for each status
+fn response_enum(
+    operation: &Operation,
+    name: &Ident,
+    roots: &mut Vec<JsonSchema>,
+    options: &Options,
+) -> TokenStream {
+    let mut variants = Vec::new();
+    let mut decode_arms = Vec::new();
+    let mut status_arms = Vec::new();
+
+    for response in &operation.responses {
+        let variant = response.status.variant();
+
+        let body = response.body.as_ref().map(|body| match body {
+            Body::Json(schema) => {
+                let hint = format_ident!("{}{}Body", operation.name, variant);
+                (body_type(schema, &hint, roots, options), true)
+            }
+            Body::Raw => (quote!(::std::vec::Vec<u8>), false),
+        });
+
+        let decode = body.as_ref().map(|(_, json)| match json {
+            true => quote!(::serde_json::from_slice(body)?),
+            false => quote!(body.to_vec()),
+        });
+
+        let doc = response.description.iter();
+
+        // the ranges and `default` keep the status they got
+        let (variant_tokens, pattern, decode_arm, status_arm) = match response.status {
+            Status::Code(code) => {
+                let code = Literal::u16_unsuffixed(code);
+                let fields = body.as_ref().map(|(ty, _)| quote!((#ty)));
+                let decoded = decode.as_ref().map(|decode| quote!((#decode)));
+                let ignored = body.as_ref().map(|_| quote!((..)));
+
+                (
+                    quote!(#variant #fields),
+                    quote!(#code),
+                    quote!(Self::#variant #decoded),
+                    quote!(Self::#variant #ignored => #code),
+                )
+            }
+            Status::Range(_) | Status::Default => {
+                let ty = body.as_ref().map(|(ty, _)| quote!(, #ty));
+                let decode = decode.as_ref().map(|decode| quote!(, #decode));
+                let pattern = match response.status {
+                    Status::Range(digit) => {
+                        let low = Literal::u16_unsuffixed(digit * 100);
+                        let high = Literal::u16_unsuffixed(digit * 100 + 99);
+                        quote!(#low..=#high)
+                    }
+                    _ => quote!(_),
+                };
+
+                (
+                    quote!(#variant(u16 #ty)),
+                    pattern,
+                    quote!(Self::#variant(status #decode)),
+                    quote!(Self::#variant(status, ..) => status),
+                )
+            }
+        };
+
+        variants.push(quote! {
+            #(#[doc = #doc])*
+            #variant_tokens
+        });
+        decode_arms.push(quote!(#pattern => #decode_arm));
+        status_arms.push(status_arm);
+    }
+
+    if !operation
+        .responses
+        .iter()
+        .any(|response| response.status == Status::Default)
+    {
+        decode_arms.push(quote! {
+            _ => return ::std::result::Result::Err(
+                ::schema2struct::ResponseError::UnexpectedStatus(status)
+            )
+        });
+    }
+
+    // the enum can derive what all of it's JSON bodies can
+    let bodies = JsonSchema {
+        one_of: Some(
+            operation
+                .responses
+                .iter()
+                .filter_map(|response| match &response.body {
+                    Some(Body::Json(schema)) => Some(schema.as_ref().clone()),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        ..Default::default()
+    };
+    let extra_derives = extra_derives(options, &bodies);
+
+    let vis = &options.vis;
+    let attrs = &options.attrs;
+    let doc = format!("The responses of `{} {}`", operation.method, operation.path);
+
+    quote! {
+        #[doc = #doc]
+        #(#attrs)*
+        #[derive(::std::clone::Clone, ::std::fmt::Debug #(, #extra_derives)*)]
+        #vis enum #name {
+            #(#variants,)*
+        }
+
+        impl #name {
+            /// Decodes a response by it's status
+            ///
+            /// # Errors
+            /// - The status isn't one of the operation's responses
+            /// - The body doesn't match the schema of it's status
+            #[allow(unused_variables)]
+            pub fn from_response(
+                status: u16,
+                body: &[u8],
+            ) -> ::std::result::Result<Self, ::schema2struct::ResponseError> {
+                ::std::result::Result::Ok(match status {
+                    #(#decode_arms,)*
+                })
+            }
+
+            /// The status of the response
+            pub fn status(&self) -> u16 {
+                match *self {
+                    #(#status_arms,)*
+                }
+            }
+        }
+    }
+}
+
+// the Rust type of a body, an object or a `oneOf` becomes a root type, named after it's component
+// or after `name` if it's not one
+fn body_type(
+    schema: &JsonSchema,
+    name: &Ident,
+    roots: &mut Vec<JsonSchema>,
+    options: &Options,
+) -> TokenStream {
+    let ty = match (mapped_type(options, Some(schema)), &schema.ty) {
+        (Some(mapped), _) => mapped,
+        (None, _) if schema.one_of.is_some() => root_type(schema, name, roots),
+        (None, JsonSchemaTypes::Object) => root_type(schema, name, roots),
+        (None, JsonSchemaTypes::Array) => {
+            let items = match schema.items.as_deref() {
+                Some(items) => body_type(items, &format_ident!("{}Item", name), roots, options),
+                None => quote!(::serde_json::Value),
+            };
+
+            quote!(::std::vec::Vec<#items>)
+        }
+        (None, JsonSchemaTypes::String) => quote!(::std::string::String),
+        (None, JsonSchemaTypes::Integer) => quote!(i64),
+        (None, JsonSchemaTypes::Number) => quote!(f64),
+        (None, JsonSchemaTypes::Boolean) => quote!(bool),
+        (None, JsonSchemaTypes::None) => quote!(::serde_json::Value),
+    };
+
+    match schema.nullable {
+        Some(true) => quote!(::std::option::Option<#ty>),
+        _ => ty,
+    }
+}
+
+fn root_type(schema: &JsonSchema, name: &Ident, roots: &mut Vec<JsonSchema>) -> TokenStream {
+    let ident = match &schema.struct_name {
+        Some(component) => type_ident(None, component),
+        None => name.clone(),
+    };
+
+    // it's the same as the component's own root, so it's generated once
+    let mut root = schema.clone();
+    root.struct_name = Some(ident.to_string());
+    root.nullable = None;
+    root.depth = 1;
+
+    roots.push(root);
+
+    quote!(#ident)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::files::{tests::temp_file, FileErrorKind};
+
+    const DOCUMENT: &str = r##"
+openapi: 3.1.0
+info: { title: Search, version: "1" }
+paths:
+  /items/{id}:
+    parameters:
+      - { name: id, in: path, schema: { type: string } }
+      - { name: limit, in: query, description: from the path item, schema: { type: integer } }
+    get:
+      operationId: getItem
+      parameters:
+        - { name: limit, in: query, required: true, schema: { type: integer, minimum: 1 } }
+        - $ref: "#/components/parameters/Query"
+      responses:
+        default: { description: anything else }
+        "404": { description: there's no such item }
+        2XX: { description: some success }
+        "200":
+          description: the item
+          content:
+            application/json:
+              schema: { type: object, properties: { id: { type: string } } }
+    delete:
+      parameters:
+        - $ref: "#/components/parameters/Loop"
+      responses:
+        "204": { description: deleted }
+    put:
+      operationId: putItem
+      parameters:
+        - $ref: "#/components/parameters/Missing"
+      responses:
+        "204": { description: replaced }
+components:
+  parameters:
+    Query: { name: q, in: query, schema: { type: string } }
+    Loop: { $ref: "#/components/parameters/Around" }
+    Around: { $ref: "#/components/parameters/Loop" }
+"##;
+
+    fn message(error: FileError) -> String {
+        match error.kind {
+            FileErrorKind::Invalid { message, .. } => message,
+            kind => panic!("expected an invalid document, got {:?}", kind),
+        }
+    }
+
+    fn document(name: &str) -> OpenApiDocument {
+        OpenApiDocument::read(&temp_file(name, DOCUMENT)).unwrap()
+    }
+
+    #[test]
+    fn statuses_are_parsed() {
+        assert!(Status::parse("200") == Some(Status::Code(200)));
+        assert!(Status::parse("2XX") == Some(Status::Range(2)));
+        assert!(Status::parse("4xx") == Some(Status::Range(4)));
+        assert!(Status::parse("default") == Some(Status::Default));
+
+        for status in ["600", "99", "6XX", "2X", "ok", ""] {
+            assert!(
+                Status::parse(status).is_none(),
+                "`{}` isn't a status",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn statuses_name_their_variants() {
+        assert_eq!(Status::Code(200).variant(), "Ok");
+        assert_eq!(Status::Code(404).variant(), "NotFound");
+        assert_eq!(Status::Code(418).variant(), "Status418");
+        assert_eq!(Status::Range(5).variant(), "Status5XX");
+        assert_eq!(Status::Default.variant(), "Default");
+    }
+
+    #[test]
+    fn responses_are_ordered_by_how_they_match() {
+        let operation = document("responses.yaml").operation("getItem").unwrap();
+
+        let statuses = operation
+            .responses
+            .iter()
+            .map(|response| response.status)
+            .collect::<Vec<_>>();
+
+        assert!(
+            statuses
+                == [
+                    Status::Code(200),
+                    Status::Code(404),
+                    Status::Range(2),
+                    Status::Default
+                ]
+        );
+        assert!(matches!(operation.responses[0].body, Some(Body::Json(_))));
+        assert!(operation.responses[1].body.is_none());
+    }
+
+    #[test]
+    fn operation_parameters_override_the_path_item_ones() {
+        let operation = document("parameters.yaml").operation("getItem").unwrap();
+
+        let path_params = operation.path_params.unwrap();
+        assert_eq!(
+            path_params.properties.unwrap().keys().collect::<Vec<_>>(),
+            ["id"]
+        );
+        assert_eq!(path_params.required, Some(vec!["id".to_owned()]));
+        assert_eq!(
+            path_params.struct_name.as_deref(),
+            Some("GetItemPathParams")
+        );
+
+        let query_params = operation.query_params.unwrap();
+        let properties = query_params.properties.as_ref().unwrap();
+
+        assert_eq!(properties.keys().collect::<Vec<_>>(), ["limit", "q"]);
+        assert_eq!(properties["limit"].minimum, Some(1.into()));
+        assert!(properties["limit"].description.is_none());
+        assert_eq!(query_params.required, Some(vec!["limit".to_owned()]));
+    }
+
+    #[test]
+    fn references_are_followed_until_they_loop() {
+        let document = document("references.yaml");
+
+        assert_eq!(
+            message(document.operation("DELETE /items/{id}").err().unwrap()),
+            "`#/components/parameters/Loop` references itself"
+        );
+        assert_eq!(
+            message(document.operation("putItem").err().unwrap()),
+            "`#/components/parameters/Missing` can't be resolved"
+        );
+        assert_eq!(
+            message(document.operation("getItems").err().unwrap()),
+            "there's no `getItems` operation, did you mean `getItem`?"
+        );
+    }
+}
@@ -0,0 +1,696 @@
+use indexmap::IndexMap;
+use proc_macro2::Span;
+use syn::{
+    braced, bracketed,
+    ext::IdentExt as _,
+    parse::{discouraged::Speculative as _, Parse, ParseStream, Parser as _},
+    spanned::Spanned as _,
+    Result as SynResult, Token,
+};
+
+use crate::{
+    checkers::{check_properties_match_required, validate_keys},
+    infer::{infer_schema, parse_examples},
+    models::{JsonSchema, JsonSchemaKeywords, JsonSchemaTypes, RenameAll},
+};
+
+macro_rules! update_schema_fields {
+    ($schema:expr, $value:expr, $key_span:expr, $value_span:expr, [
+        $($field:ident),* $(,)?
+    ]) => {
+        $(
+            if $schema.$field.is_none() && $value.$field.is_some() {
+                $schema.$field = $value.$field;
+
+                paste::paste! {
+                    $schema.[<$field _span>] = Some(($key_span, $value_span));
+                }
+            }
+        )*
+    };
+}
+
+/// Tracks where a schema is being parsed
+///
+/// It's passed down from the root to every nested schema, so every macro call
+/// starts fresh and sibling schemas get the same depth
+#[derive(Clone, Copy, Debug)]
+struct ParseContext {
+    /// 1 for the root schema, and increments for each nested schema level
+    depth: usize,
+}
+
+impl ParseContext {
+    fn root() -> Self {
+        Self { depth: 1 }
+    }
+
+    fn nested(self) -> Self {
+        Self {
+            depth: self.depth + 1,
+        }
+    }
+
+    /// parses a nested schema from it's tokens, one level deeper than the current one
+    fn parse_nested(self, tokens: proc_macro2::TokenStream) -> SynResult<JsonSchema> {
+        (|input: ParseStream| parse_schema(input, self.nested())).parse2(tokens)
+    }
+}
+
+impl Parse for JsonSchema {
+    fn parse(input: ParseStream) -> SynResult<Self> {
+        parse_schema(input, ParseContext::root())
+    }
+}
+
+/// parses a single schema, the root or a nested one depending on the context
+fn parse_schema(input: ParseStream, ctx: ParseContext) -> SynResult<JsonSchema> {
+    let mut schema = JsonSchema {
+        current_key_span: Some(input.span()),
+        depth: ctx.depth,
+        ..Default::default()
+    };
+
+    let mut first_item = true;
+
+    // an invalid `type` is already reported, so it shouldn't be reported again as missing
+    let mut has_type_key = false;
+
+    while !input.is_empty() {
+        // a missing comma is reported, and the next key is still parsed
+        if !first_item {
+            if let Err(e) = input.parse::<Token![,]>() {
+                emit_error!(e.span(), e);
+            }
+        }
+
+        if input.is_empty() {
+            break;
+        }
+
+        first_item = false;
+
+        let key = match input.call(syn::Ident::parse_any) {
+            Ok(key) => key,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                skip_value(input)?;
+                continue;
+            }
+        };
+        let key_str = key.to_string();
+        let key_span = key.span();
+
+        has_type_key |= key_str == "type";
+
+        if let Err(e) = input.parse::<Token![:]>() {
+            emit_error!(e.span(), e);
+        }
+
+        let is_brace = input.peek(syn::token::Brace);
+
+        if matches!(key_str.as_str(), "properties") && !is_brace {
+            emit_error!(key_span, "expected `properties: {key: {...}, ...}`");
+            skip_value(input)?;
+            continue;
+        }
+
+        // every problem gets reported and the value skipped, so the rest of the schema still gets checked
+        match key_str.as_str() {
+            "properties" => {
+                let Some(Properties { span, properties }) =
+                    parse_or_skip(input, |input| handle_properties(&input, ctx))?
+                else {
+                    continue;
+                };
+
+                schema.properties = Some(properties);
+                schema.properties_span = Some((key_span, span));
+
+                // we must continue and not further parse, as it's not really needed
+                continue;
+            }
+            "items" => {
+                let Some(Items { span, items_type }) =
+                    parse_or_skip(input, |input| handle_items(&schema, &input, &key_span, ctx))?
+                else {
+                    continue;
+                };
+
+                // we can either use
+                //
+                // items: string
+                //
+                // or
+                //
+                // items: { type: string }
+                let type_schema = match items_type {
+                    ItemsValue::Block(s) => s,
+                    ItemsValue::Type(t) => Box::new(JsonSchema {
+                        ty: t,
+                        ..Default::default()
+                    }),
+                };
+
+                schema.items = Some(type_schema);
+                schema.items_span = Some((key_span, span));
+                continue;
+            }
+
+            "contains" => {
+                let Some(Contains { span, contains }) = parse_or_skip(input, |input| {
+                    handle_contains(&schema, &input, &key_span, ctx)
+                })?
+                else {
+                    continue;
+                };
+
+                let contains_schema = JsonSchema {
+                    ty: contains,
+                    ..Default::default()
+                };
+
+                schema.contains = Some(Box::new(contains_schema));
+                schema.contains_span = Some((key_span, span));
+                continue;
+            }
+
+            "derive" => {
+                let Some(Derives { span, derives }) =
+                    parse_or_skip(input, |input| handle_derives(&input))?
+                else {
+                    continue;
+                };
+
+                schema.derives = Some(derives);
+                schema.derives_span = Some((key_span, span));
+                continue;
+            }
+
+            "attrs" => {
+                let Some(Attrs { span, attrs }) =
+                    parse_or_skip(input, |input| handle_attrs(&input))?
+                else {
+                    continue;
+                };
+
+                schema.attrs = Some(attrs);
+                schema.attrs_span = Some((key_span, span));
+                continue;
+            }
+
+            // `pub(crate)` is not an expression, so it can't go through `syn::Expr`
+            "vis" => {
+                let Some(vis) = parse_or_skip(input, |input| input.parse::<syn::Visibility>())?
+                else {
+                    continue;
+                };
+
+                schema.vis_span = Some((key_span, vis.span()));
+                schema.vis = Some(vis);
+                continue;
+            }
+
+            "rename_all" => {
+                let Some(rename_all) = parse_or_skip(input, |input| {
+                    let rename_all = handle_rename_all(&input)?;
+                    let span = rename_all.span();
+
+                    Ok((RenameAll::try_from(rename_all)?, span))
+                })?
+                else {
+                    continue;
+                };
+
+                schema.rename_all_span = Some((key_span, rename_all.1));
+                schema.rename_all = Some(rename_all.0);
+                continue;
+            }
+
+            _ => {}
+        };
+
+        // an unknown keyword can have any value, so there's no point in parsing it
+        if let Err(e) = JsonSchemaKeywords::try_from(key.clone()) {
+            emit_error!(e.span(), e);
+            skip_value(input)?;
+            continue;
+        }
+
+        _ = input.parse::<Token![,]>();
+
+        let Some(value_expr) = parse_or_skip(input, |input| input.parse::<syn::Expr>())? else {
+            continue;
+        };
+        let value_span = value_expr.span();
+
+        let value = match JsonSchema::try_from((key, value_expr)) {
+            Ok(value) => value,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                continue;
+            }
+        };
+
+        // if the main schema is none and the other is not none, then we add items
+        //
+        // otherwise we skip
+        if matches!(schema.ty, JsonSchemaTypes::None) && !matches!(value.ty, JsonSchemaTypes::None)
+        {
+            schema.ty = value.ty.clone();
+            schema.ty_span = Some((key_span, value_span));
+        }
+
+        merge_keyword(&mut schema, value, key_span, value_span);
+    }
+
+    if ctx.depth == 1 {
+        infer_from_examples(&mut schema);
+    }
+
+    if schema.required.is_some() && schema.properties.is_none() {
+        if let Some((_, required_span)) = schema.required_span {
+            emit_error!(
+                required_span,
+                "make sure to implement what's in the required"
+            );
+        }
+    }
+
+    if matches!(schema.ty, JsonSchemaTypes::None) && !has_type_key && schema.from_example.is_none()
+    {
+        if let Some(current_key_span) = schema.current_key_span {
+            emit_error!(current_key_span, "`type` must be set");
+        }
+    }
+
+    check_properties_match_required(&schema);
+
+    validate_keys(&schema);
+
+    Ok(schema)
+}
+
+/// Merges a schema holding a single keyword into the schema being built, along with it's spans
+///
+/// a keyword that's already set is kept as is
+pub(crate) fn merge_keyword(
+    schema: &mut JsonSchema,
+    value: JsonSchema,
+    key_span: Span,
+    value_span: Span,
+) {
+    // a helper macro that basiclly does
+    //
+    // ```rust
+    //  if schema.#something.is_none() && value.#something.is_some() {
+    //      schema.#something = value.#something;
+    //      schema.#something_span = Some((key, value_span));
+    //  }
+    // ```
+    update_schema_fields!(
+        schema,
+        value,
+        key_span,
+        value_span,
+        [
+            minimum,
+            maximum,
+            min_items,
+            max_items,
+            unique_items,
+            contains,
+            default,
+            examples,
+            enum_values,
+            min_lenght,
+            max_lenght,
+            pattern,
+            format,
+            const_value,
+            description,
+            required,
+            properties,
+            title,
+            struct_name,
+            module,
+            from_example,
+        ]
+    );
+}
+
+/// Fills the schema from it's `from_example` documents, the other keys are kept
+///
+/// # Errors
+/// - An example isn't valid JSON, or it's not an object
+/// - The schema already has `properties` or `required`, or a type other than `object`
+fn infer_from_examples(schema: &mut JsonSchema) {
+    let Some((examples, (key_span, value_span))) =
+        schema.from_example.as_ref().zip(schema.from_example_span)
+    else {
+        return;
+    };
+
+    let inferred_keys = [
+        (schema.properties_span, "properties"),
+        (schema.required_span, "required"),
+    ];
+
+    let mut conflicts = false;
+
+    for (span, key) in inferred_keys {
+        if let Some((span, _)) = span {
+            emit_error!(
+                span,
+                "`{}` can't be used along with `from_example`, it's inferred from the examples",
+                key
+            );
+            conflicts = true;
+        }
+    }
+
+    if conflicts {
+        return;
+    }
+
+    if !matches!(schema.ty, JsonSchemaTypes::None | JsonSchemaTypes::Object) {
+        if let Some((ty_span, _)) = schema.ty_span {
+            emit_error!(ty_span, "`from_example` can only infer an object type");
+        }
+        return;
+    }
+
+    // it's an object even if the examples are wrong, so it's only reported once
+    schema.ty = JsonSchemaTypes::Object;
+    schema.ty_span.get_or_insert((key_span, value_span));
+
+    let inferred = match parse_examples(examples.iter().map(String::as_str)) {
+        Ok(samples) => infer_schema(&samples),
+        Err(e) => Err(format!("invalid example: {}", e)),
+    };
+
+    match inferred {
+        Ok(inferred) => {
+            schema.properties = inferred.properties;
+            schema.required = inferred.required;
+        }
+        Err(e) => emit_error!(value_span, e),
+    }
+}
+
+/// used for the result of properties handlation
+struct Properties {
+    span: Span,
+    properties: IndexMap<String, JsonSchema>,
+}
+
+fn handle_properties(input: &ParseStream, ctx: ParseContext) -> Result<Properties, syn::Error> {
+    let content;
+    braced!(content in input);
+
+    let mut properties = IndexMap::new();
+    let properties_span = content.span();
+
+    let mut in_property_first_item = true;
+
+    // TODO: improve this, doing too much
+    // this is because a tralling comma will be counted as a tokenstream thus the
+    // while check is still fine and get's in, but we check after the comma, if
+    // it's empty then we stop because that's because of the tralling comma
+    //
+    // a broken property is reported and skipped, so the ones after it still get checked
+    while !content.is_empty() {
+        if !in_property_first_item {
+            if let Err(e) = content.parse::<Token![,]>() {
+                emit_error!(e.span(), e);
+            }
+        }
+
+        if content.is_empty() {
+            break;
+        }
+
+        in_property_first_item = false;
+
+        let property_key: syn::LitStr = match content.parse() {
+            Ok(property_key) => property_key,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                skip_value(&content)?;
+                continue;
+            }
+        };
+
+        if let Err(e) = content.parse::<Token![:]>() {
+            emit_error!(e.span(), e);
+        }
+
+        let group = match content.parse::<proc_macro2::Group>() {
+            Ok(group) if group.delimiter() == proc_macro2::Delimiter::Brace => group,
+            Ok(group) => {
+                emit_error!(group.span(), "Expected a brace-delimited group");
+                continue;
+            }
+            Err(e) => {
+                emit_error!(e.span(), e);
+                skip_value(&content)?;
+                continue;
+            }
+        };
+
+        let mut property_schema = match ctx.parse_nested(group.stream()) {
+            Ok(property_schema) => property_schema,
+            Err(e) => {
+                emit_error!(e.span(), e);
+                continue;
+            }
+        };
+
+        // the key is what points to the property, so the generation errors can use it
+        property_schema.current_key_span = Some(property_key.span());
+
+        properties.insert(property_key.value(), property_schema);
+    }
+    _ = content.parse::<Token![,]>();
+
+    Ok(Properties {
+        span: properties_span,
+        properties,
+    })
+}
+
+/// used as a result for handling the items values
+enum ItemsValue {
+    Block(Box<JsonSchema>),
+    Type(JsonSchemaTypes),
+}
+
+struct Items {
+    span: Span,
+    items_type: ItemsValue,
+}
+
+fn handle_items(
+    schema: &JsonSchema,
+    input: &ParseStream,
+    key_span: &Span,
+    ctx: ParseContext,
+) -> Result<Items, syn::Error> {
+    if input.peek(syn::Ident) {
+        let type_ident: syn::Ident = input.parse()?;
+        let type_ident_span = type_ident.span();
+
+        let items_type = JsonSchemaTypes::try_from(type_ident)?;
+
+        if schema.items.is_none() {
+            Ok(Items {
+                span: type_ident_span,
+                items_type: ItemsValue::Type(items_type),
+            })
+        } else {
+            Err(syn::Error::new(type_ident_span, "remove duplicated keys"))
+        }
+    } else if input.peek(syn::token::Brace) {
+        let group: proc_macro2::Group = input.parse()?;
+
+        let nested_tokens = group.stream();
+        let nested_tokens_span = nested_tokens.span();
+
+        let nested_schema = ctx.parse_nested(nested_tokens)?;
+
+        if schema.items.is_none() {
+            Ok(Items {
+                span: nested_tokens_span,
+                items_type: ItemsValue::Block(Box::new(nested_schema)),
+            })
+        } else {
+            Err(syn::Error::new(
+                nested_tokens_span,
+                "remove duplicated keys",
+            ))
+        }
+    } else {
+        Err(syn::Error::new(
+            *key_span,
+            "`items` value must be eithr a type `items: string` or a nested schema",
+        ))
+    }
+}
+
+/// used as a result for handling the contains values
+struct Contains {
+    span: Span,
+    contains: JsonSchemaTypes,
+}
+
+fn handle_contains(
+    schema: &JsonSchema,
+    input: &ParseStream,
+    key_span: &Span,
+    ctx: ParseContext,
+) -> Result<Contains, syn::Error> {
+    if input.peek(syn::Ident) {
+        let contains_ident: syn::Ident = input.parse()?;
+        let contains_ident_span = contains_ident.span();
+
+        let contains = JsonSchemaTypes::try_from(contains_ident)?;
+
+        if schema.contains.is_none() {
+            Ok(Contains {
+                span: contains_ident_span,
+                contains,
+            })
+        } else {
+            Err(syn::Error::new(
+                contains_ident_span,
+                "remove duplicated keys",
+            ))
+        }
+    } else if input.peek(syn::token::Brace) {
+        let group: proc_macro2::Group = input.parse()?;
+
+        let nested_tokens = group.stream();
+        let nested_tokens_span = nested_tokens.span();
+
+        let nested_schema = ctx.parse_nested(nested_tokens)?;
+
+        if schema.contains.is_none() {
+            Ok(Contains {
+                span: nested_tokens_span,
+                contains: nested_schema.ty,
+            })
+        } else {
+            Err(syn::Error::new(
+                nested_tokens_span,
+                "remove duplicated keys",
+            ))
+        }
+    } else {
+        Err(syn::Error::new(
+            *key_span,
+            "`contains` value must be eithr a type `contains: string` or a nested schema",
+        ))
+    }
+}
+
+/// used as a result for handling the derive values
+pub(crate) struct Derives {
+    pub span: Span,
+    pub derives: Vec<syn::Path>,
+}
+
+/// parses `derive: [PartialEq, Eq, ::std::hash::Hash]`
+pub(crate) fn handle_derives(input: &ParseStream) -> Result<Derives, syn::Error> {
+    let content;
+    bracketed!(content in input);
+
+    let span = content.span();
+    let derives = content
+        .parse_terminated(syn::Path::parse_mod_style, Token![,])?
+        .into_iter()
+        .collect();
+
+    Ok(Derives { span, derives })
+}
+
+/// used as a result for handling the attrs values
+pub(crate) struct Attrs {
+    pub span: Span,
+    pub attrs: Vec<syn::Attribute>,
+}
+
+/// parses `attrs: [#[non_exhaustive], #[doc = "..."]]`, the commas are optional
+pub(crate) fn handle_attrs(input: &ParseStream) -> Result<Attrs, syn::Error> {
+    let content;
+    bracketed!(content in input);
+
+    let span = content.span();
+    let mut attrs = Vec::new();
+
+    while !content.is_empty() {
+        attrs.extend(content.call(syn::Attribute::parse_outer)?);
+
+        _ = content.parse::<Option<Token![,]>>()?;
+    }
+
+    Ok(Attrs { span, attrs })
+}
+
+/// parses the rename rule, either as a string `rename_all: "kebab-case"`
+/// or written as is `rename_all: kebab-case`
+pub(crate) fn handle_rename_all(input: &ParseStream) -> Result<syn::LitStr, syn::Error> {
+    if input.peek(syn::LitStr) {
+        return input.parse();
+    }
+
+    let first = input.call(syn::Ident::parse_any)?;
+    let span = first.span();
+    let mut rule = first.to_string();
+
+    while input.peek(Token![-]) {
+        input.parse::<Token![-]>()?;
+        rule.push('-');
+        rule.push_str(&input.call(syn::Ident::parse_any)?.to_string());
+    }
+
+    Ok(syn::LitStr::new(&rule, span))
+}
+
+/// runs a value parser on a fork of the input
+///
+/// on success the input moves past the value, otherwise the error gets reported
+/// and the value skipped, so the parsing can go on with the next key
+fn parse_or_skip<T>(
+    input: ParseStream,
+    parser: impl FnOnce(ParseStream) -> SynResult<T>,
+) -> SynResult<Option<T>> {
+    let fork = input.fork();
+
+    match parser(&fork) {
+        Ok(value) => {
+            input.advance_to(&fork);
+            Ok(Some(value))
+        }
+        Err(e) => {
+            emit_error!(e.span(), e);
+            skip_value(input)?;
+            Ok(None)
+        }
+    }
+}
+
+/// skips every token up to the next `,`, which is where the next key starts
+fn skip_value(input: ParseStream) -> SynResult<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+
+        while let Some((token, next)) = rest.token_tree() {
+            match token {
+                proc_macro2::TokenTree::Punct(punct) if punct.as_char() == ',' => break,
+                _ => rest = next,
+            }
+        }
+
+        Ok(((), rest))
+    })
+}
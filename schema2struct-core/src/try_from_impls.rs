@@ -0,0 +1,545 @@
+use syn::spanned::Spanned as _;
+
+use crate::{
+    checkers::closest_match,
+    models::{
+        Formats, JsonSchema, JsonSchemaKeywords, JsonSchemaTypes, JsonSchemaValues, RenameAll,
+    },
+};
+
+/// builds the error for an unknown value, suggesting the closest known one if there's any
+fn unknown_value_error(value: &syn::Ident, message: &str, known: &[&str]) -> syn::Error {
+    match closest_match(&value.to_string(), known) {
+        Some(suggestion) => syn::Error::new(
+            value.span(),
+            format!("{}, did you mean `{}`?", message, suggestion),
+        ),
+        None => syn::Error::new(value.span(), message),
+    }
+}
+
+// ----
+impl TryFrom<syn::Ident> for JsonSchemaTypes {
+    type Error = syn::Error;
+
+    fn try_from(value: syn::Ident) -> std::result::Result<Self, Self::Error> {
+        match value.to_string().as_str() {
+            "array" => Ok(Self::Array),
+            "object" => Ok(Self::Object),
+            "string" => Ok(Self::String),
+            "number" => Ok(Self::Number),
+            "integer" => Ok(Self::Integer),
+            "boolean" => Ok(Self::Boolean),
+            _ => Err(unknown_value_error(&value, "Unknown type", &Self::NAMES)),
+        }
+    }
+}
+// ----
+
+// ----
+impl TryFrom<syn::Expr> for JsonSchemaValues {
+    type Error = syn::Error;
+
+    fn try_from(value: syn::Expr) -> std::result::Result<Self, Self::Error> {
+        match value {
+            syn::Expr::Path(path) if path.path.segments.len() == 1 => {
+                let ident = path
+                    .path
+                    .segments
+                    .first()
+                    .expect("We already checked the length")
+                    .clone()
+                    .ident;
+
+                Ok(JsonSchemaValues::Ident(ident))
+            }
+
+            syn::Expr::Lit(literal) => match literal.lit {
+                syn::Lit::Str(s) => Ok(JsonSchemaValues::Str(s.value())),
+                lit @ (syn::Lit::Int(_) | syn::Lit::Float(_)) => number(&lit, false),
+                syn::Lit::Bool(b) => Ok(JsonSchemaValues::Bool(b.value)),
+                syn::Lit::Char(ch) => Ok(JsonSchemaValues::Char(ch.value())),
+                _ => Err(syn::Error::new(literal.span(), "invalid literal")),
+            },
+            syn::Expr::Array(array) => {
+                let mut elements = vec![];
+                for element in array.elems {
+                    elements.push(JsonSchemaValues::try_from(element)?);
+                }
+
+                Ok(JsonSchemaValues::Array(elements))
+            }
+
+            // a negative number is a literal behind a minus, like `-40` or `-0.5`
+            syn::Expr::Unary(syn::ExprUnary {
+                op: syn::UnOp::Neg(_),
+                expr,
+                ..
+            }) => match *expr {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: lit @ (syn::Lit::Int(_) | syn::Lit::Float(_)),
+                    ..
+                }) => number(&lit, true),
+                expr => Err(syn::Error::new(
+                    expr.span(),
+                    "only a number can be negative",
+                )),
+            },
+
+            _ => Err(syn::Error::new(value.span(), "Unsupported expression type")),
+        }
+    }
+}
+
+// an integer or a float literal, it has to fit in a 64-bit integer or be a finite float
+fn number(lit: &syn::Lit, negative: bool) -> syn::Result<JsonSchemaValues> {
+    let sign = if negative { "-" } else { "" };
+
+    let number = match lit {
+        syn::Lit::Int(int) => {
+            let digits = format!("{}{}", sign, int.base10_digits());
+
+            // a positive one can go up to `u64::MAX`
+            let number = match negative {
+                true => digits.parse::<i64>().map(serde_json::Number::from).ok(),
+                false => digits.parse::<u64>().map(serde_json::Number::from).ok(),
+            };
+
+            number.ok_or_else(|| {
+                syn::Error::new(int.span(), "the number doesn't fit in a 64-bit integer")
+            })?
+        }
+        syn::Lit::Float(float) => {
+            let value = format!("{}{}", sign, float.base10_digits())
+                .parse::<f64>()
+                .map_err(|e| syn::Error::new(float.span(), e))?;
+
+            serde_json::Number::from_f64(value)
+                .ok_or_else(|| syn::Error::new(float.span(), "a number must be finite"))?
+        }
+        _ => unreachable!("only called with a number literal"),
+    };
+
+    Ok(JsonSchemaValues::Number(number))
+}
+// ----
+
+// ----
+impl TryFrom<syn::Ident> for JsonSchemaKeywords {
+    type Error = syn::Error;
+
+    fn try_from(value: syn::Ident) -> std::result::Result<Self, Self::Error> {
+        match value.to_string().as_str() {
+            "type" => Ok(JsonSchemaKeywords::Type),
+            "title" => Ok(JsonSchemaKeywords::Title),
+            "required" => Ok(JsonSchemaKeywords::Required),
+            "description" => Ok(JsonSchemaKeywords::Description),
+            "items" => Ok(JsonSchemaKeywords::Items),
+            "properties" => Ok(JsonSchemaKeywords::Properties),
+            "default" => Ok(JsonSchemaKeywords::Default),
+            "examples" => Ok(JsonSchemaKeywords::Examples),
+            "enum" => Ok(JsonSchemaKeywords::Enum),
+            "const" => Ok(JsonSchemaKeywords::Const),
+            "min_length" => Ok(JsonSchemaKeywords::MinLength),
+            "max_length" => Ok(JsonSchemaKeywords::MaxLenght),
+            "pattern" => Ok(JsonSchemaKeywords::Pattern),
+            "format" => Ok(JsonSchemaKeywords::Format),
+            "minimum" => Ok(JsonSchemaKeywords::Minimum),
+            "maximum" => Ok(JsonSchemaKeywords::Maximum),
+            "max_items" => Ok(JsonSchemaKeywords::MaxItems),
+            "min_items" => Ok(JsonSchemaKeywords::MinItems),
+            "unique_items" => Ok(JsonSchemaKeywords::UniqueItems),
+            "contains" => Ok(JsonSchemaKeywords::Contains),
+            "struct" => Ok(JsonSchemaKeywords::Struct),
+            "derive" => Ok(JsonSchemaKeywords::Derive),
+            "attrs" => Ok(JsonSchemaKeywords::Attrs),
+            "vis" => Ok(JsonSchemaKeywords::Vis),
+            "mod" => Ok(JsonSchemaKeywords::Mod),
+            "rename_all" => Ok(JsonSchemaKeywords::RenameAll),
+            "from_example" => Ok(JsonSchemaKeywords::FromExample),
+            _ => Err(unknown_value_error(
+                &value,
+                "Unknown keyword",
+                &JsonSchemaKeywords::NAMES,
+            )),
+        }
+    }
+}
+// ----
+
+// ----
+
+impl TryFrom<syn::Ident> for Formats {
+    type Error = syn::Error;
+
+    fn try_from(value: syn::Ident) -> Result<Self, Self::Error> {
+        match value.to_string().as_str() {
+            "date" => Ok(Formats::Date),
+            "time" => Ok(Formats::Time),
+            // `date-time` isn't an ident, it's still `date-time` in the generated schema
+            "datetime" => Ok(Formats::DateTime),
+            "email" => Ok(Formats::Email),
+            "hostname" => Ok(Formats::Hostname),
+            "ipv4" => Ok(Formats::Ipv4),
+            "ipv6" => Ok(Formats::Ipv6),
+            "uri" => Ok(Formats::Uri),
+            "uuid" => Ok(Formats::Uuid),
+            _ => {
+             Err(unknown_value_error(
+                    &value,
+                    "unsupported format, avaliables are: `date`, `time`, `datetime`, `email`, `hostname`, `ipv4`, `ipv6`, `uri`, `uuid`",
+                    &Formats::NAMES,
+                ))
+            }
+        }
+    }
+}
+
+// ----
+
+// ----
+impl TryFrom<syn::LitStr> for RenameAll {
+    type Error = syn::Error;
+
+    fn try_from(value: syn::LitStr) -> Result<Self, Self::Error> {
+        match value.value().as_str() {
+            "lowercase" => Ok(RenameAll::Lower),
+            "UPPERCASE" => Ok(RenameAll::Upper),
+            "PascalCase" => Ok(RenameAll::Pascal),
+            "camelCase" => Ok(RenameAll::Camel),
+            "snake_case" => Ok(RenameAll::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(RenameAll::ScreamingSnake),
+            "kebab-case" => Ok(RenameAll::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(RenameAll::ScreamingKebab),
+            _ => {
+             Err(syn::Error::new(
+                    value.span(),
+                    "unsupported rename rule, avaliables are: `lowercase`, `UPPERCASE`, `PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`, `SCREAMING-KEBAB-CASE`",
+                ))
+            }
+        }
+    }
+}
+// ----
+
+// ---
+impl TryFrom<(syn::Ident, syn::Expr)> for JsonSchema {
+    type Error = syn::Error;
+
+    fn try_from(value: (syn::Ident, syn::Expr)) -> std::result::Result<Self, Self::Error> {
+        let key = value.0;
+        let value = value.1;
+        let value_span = value.span();
+
+        let schema_key = JsonSchemaKeywords::try_from(key)?;
+        let schema_value = JsonSchemaValues::try_from(value)?;
+
+        let mut schema = Self::default();
+
+        match schema_key {
+            JsonSchemaKeywords::Type => match schema_value {
+                JsonSchemaValues::Ident(ident) => schema.ty = JsonSchemaTypes::try_from(ident)?,
+                _ => return Err(syn::Error::new(value_span, "Invalid type")),
+            },
+
+            JsonSchemaKeywords::Struct => match schema_value {
+                JsonSchemaValues::Ident(ident) => schema.struct_name = Some(ident.to_string()),
+                _ => return Err(syn::Error::new(value_span, "only idents are allowed")),
+            },
+
+            JsonSchemaKeywords::Mod => match schema_value {
+                JsonSchemaValues::Ident(ident) => schema.module = Some(ident),
+                _ => return Err(syn::Error::new(value_span, "only idents are allowed")),
+            },
+
+            JsonSchemaKeywords::UniqueItems => match schema_value {
+                JsonSchemaValues::Bool(b) => schema.unique_items = Some(b),
+                _ => return Err(syn::Error::new(value_span, "only boolean is allowed")),
+            },
+
+            JsonSchemaKeywords::MinItems => match schema_value {
+                JsonSchemaValues::Number(num) if num.is_u64() => {
+                    schema.min_items = num.as_u64().map(|num| num as usize)
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "only a positive integer is allowed",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::MaxItems => match schema_value {
+                JsonSchemaValues::Number(num) if num.is_u64() => {
+                    schema.max_items = num.as_u64().map(|num| num as usize)
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "only a positive integer is allowed",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::Minimum => match schema_value {
+                JsonSchemaValues::Number(num) => schema.minimum = Some(num),
+                _ => return Err(syn::Error::new(value_span, "only number is allowed")),
+            },
+            JsonSchemaKeywords::Maximum => match schema_value {
+                JsonSchemaValues::Number(num) => schema.maximum = Some(num),
+                _ => return Err(syn::Error::new(value_span, "only number is allowed")),
+            },
+
+            JsonSchemaKeywords::MinLength => match schema_value {
+                JsonSchemaValues::Number(num) if num.is_u64() => {
+                    schema.min_lenght = num.as_u64().map(|num| num as usize)
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "only a positive integer is allowed",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::MaxLenght => match schema_value {
+                JsonSchemaValues::Number(num) if num.is_u64() => {
+                    schema.max_lenght = num.as_u64().map(|num| num as usize)
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "only a positive integer is allowed",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::Pattern => match schema_value {
+                JsonSchemaValues::Str(s) => schema.pattern = Some(s),
+                _ => return Err(syn::Error::new(value_span, "only string is allowed")),
+            },
+
+            JsonSchemaKeywords::Format => match schema_value {
+                JsonSchemaValues::Ident(ident) => {
+                    let format = Formats::try_from(ident)?;
+
+                    schema.format = Some(format);
+                }
+                // a string is written as in JSON Schema, so `"date-time"` can be written as is
+                JsonSchemaValues::Str(format) => {
+                    schema.format = Some(
+                        serde_json::from_value(serde_json::Value::String(format)).map_err(|_| {
+                            syn::Error::new(
+                                value_span,
+                                "unsupported format, avaliables are: `date`, `time`, `date-time`, `email`, `hostname`, `ipv4`, `ipv6`, `uri`, `uuid`",
+                            )
+                        })?,
+                    );
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "only idents and strings are supported",
+                    ))
+                }
+            },
+            JsonSchemaKeywords::Examples => match schema_value {
+                JsonSchemaValues::Array(examples) => {
+                    for example in examples.iter() {
+                        if !matches!(example, JsonSchemaValues::Str(_)) {
+                            return Err(syn::Error::new(
+                                value_span,
+                                "examples should all be string",
+                            ));
+                        }
+                    }
+
+                    let examples = examples
+                        .iter()
+                        .map(|value| {
+                            value
+                                .get_str()
+                                .cloned()
+                                .expect("We already checked they're all strings")
+                                .into()
+                        })
+                        .collect();
+
+                    schema.examples = Some(examples);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "examples should be inside of an array",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::Enum => match schema_value {
+                JsonSchemaValues::Array(enum_values) => {
+                    for value in enum_values.iter() {
+                        if let JsonSchemaValues::Ident(ident) = value {
+                            return Err(syn::Error::new(
+                                ident.span(),
+                                "enum should contain values, not idents",
+                            ));
+                        }
+                    }
+
+                    schema.enum_values = Some(enum_values)
+                }
+
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "enum should be inside of an array",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::Const => match schema_value {
+                JsonSchemaValues::Ident(ident) => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "const value can't be an ident",
+                    ))
+                }
+                JsonSchemaValues::Array(_) => {
+                    return Err(syn::Error::new(value_span, "const value can't be an array"))
+                }
+                value => schema.const_value = Some(value),
+            },
+
+            JsonSchemaKeywords::Default => match schema_value {
+                JsonSchemaValues::Ident(ident) => {
+                    return Err(syn::Error::new(
+                        ident.span(),
+                        "default value can't be an ident",
+                    ))
+                }
+                value => schema.default = Some(value),
+            },
+
+            JsonSchemaKeywords::Title => match schema_value {
+                JsonSchemaValues::Str(s) => {
+                    schema.title = Some(s);
+                }
+                _ => return Err(syn::Error::new(value_span, "title must be a string")),
+            },
+            JsonSchemaKeywords::Description => match schema_value {
+                JsonSchemaValues::Str(s) => schema.description = Some(s),
+                _ => return Err(syn::Error::new(value_span, "description must be a string")),
+            },
+
+            JsonSchemaKeywords::Required => match schema_value {
+                JsonSchemaValues::Array(array) => {
+                    let are_all_str = array.iter().all(|v| matches!(v, JsonSchemaValues::Str(_)));
+
+                    if !are_all_str {
+                        return Err(syn::Error::new(value_span, "the array must be all string"));
+                    }
+
+                    let collected_items = array
+                        .into_iter()
+                        .filter_map(|item| match item {
+                            JsonSchemaValues::Str(s) => Some(s),
+                            _ => None,
+                        })
+                        .collect();
+
+                    schema.required = Some(collected_items);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "the `required` field must be an array",
+                    ))
+                }
+            },
+
+            // a single example, or a list of them to merge
+            JsonSchemaKeywords::FromExample => match schema_value {
+                JsonSchemaValues::Str(example) => schema.from_example = Some(vec![example]),
+                JsonSchemaValues::Array(examples) => {
+                    let examples = examples
+                        .into_iter()
+                        .map(|example| match example {
+                            JsonSchemaValues::Str(example) => Ok(example),
+                            _ => Err(syn::Error::new(
+                                value_span,
+                                "every example must be a JSON string",
+                            )),
+                        })
+                        .collect::<Result<_, _>>()?;
+
+                    schema.from_example = Some(examples);
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        value_span,
+                        "`from_example` must be a JSON string, or an array of them",
+                    ))
+                }
+            },
+
+            JsonSchemaKeywords::Properties => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::Items => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::Contains => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::Derive => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::Attrs => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::Vis => unreachable!("it's already handled at parsing"),
+            JsonSchemaKeywords::RenameAll => unreachable!("it's already handled at parsing"),
+        }
+
+        Ok(schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn value(expr: syn::Expr) -> syn::Result<serde_json::Value> {
+        JsonSchemaValues::try_from(expr).map(|value| serde_json::to_value(value).unwrap())
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(value(syn::parse_quote!(40)).unwrap(), 40);
+        assert_eq!(value(syn::parse_quote!(-40)).unwrap(), -40);
+        assert_eq!(value(syn::parse_quote!(-0.5)).unwrap(), -0.5);
+        assert_eq!(value(syn::parse_quote!(1e3)).unwrap(), 1000.0);
+        assert_eq!(
+            value(syn::parse_quote!(18446744073709551615)).unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            value(syn::parse_quote!([-1, 2])).unwrap(),
+            serde_json::json!([-1, 2])
+        );
+    }
+
+    #[test]
+    fn invalid_numbers() {
+        assert_eq!(
+            value(syn::parse_quote!(18446744073709551616))
+                .unwrap_err()
+                .to_string(),
+            "the number doesn't fit in a 64-bit integer"
+        );
+        assert_eq!(
+            value(syn::parse_quote!(-9223372036854775809))
+                .unwrap_err()
+                .to_string(),
+            "the number doesn't fit in a 64-bit integer"
+        );
+        assert_eq!(
+            value(syn::parse_quote!(1e999)).unwrap_err().to_string(),
+            "a number must be finite"
+        );
+        assert_eq!(
+            value(syn::parse_quote!(-"a")).unwrap_err().to_string(),
+            "only a number can be negative"
+        );
+    }
+}
@@ -0,0 +1,442 @@
+/// TypeScript module
+///
+/// Generates the TypeScript declarations (`.d.ts`) of the same schemas the Rust types come from,
+/// so a frontend reads the same payloads without the two drifting apart
+///
+/// # Declarations
+/// - An object becomes an `interface`, named like it's Rust struct
+/// - A property that's not `required` is optional with `?`, and a nullable one is `| null`
+/// - An `enum` becomes a union of it's literals, and a `const` is the literal itself
+/// - A `oneOf` becomes a union type, a tagged one keeps the tag in every interface
+/// - A map becomes a `Record<string, T>`, and an array `T[]`
+/// - The `description` becomes the JSDoc
+/// - A schema that accepts any value is `unknown`
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use syn::Ident;
+
+use crate::{
+    generator::Options,
+    models::{JsonSchema, JsonSchemaTypes, JsonSchemaValues},
+    naming::{field_ident, type_ident},
+};
+
+/// Generates the declarations of several root schemas into a single `.d.ts` file
+///
+/// a type they share, like a referenced record, is only declared once, and the keys are the ones
+/// on the wire, so the `rename_all` of the options is applied like serde does
+///
+/// # Arguments
+/// * `schemas` - The root schemas, named after their struct name or their `title`
+/// * `options` - The options the Rust types are generated with
+pub fn generate_declarations(schemas: &[JsonSchema], options: &Options) -> String {
+    let mut declarations = Declarations {
+        options,
+        declared: HashSet::new(),
+        output: Vec::new(),
+    };
+
+    for schema in schemas {
+        let name = schema
+            .struct_name
+            .as_ref()
+            .or(schema.title.as_ref())
+            .map(String::as_str)
+            .unwrap_or_default();
+
+        declarations.declare(&type_ident(None, name), schema);
+    }
+
+    let mut output = "// @generated by schema2struct, do not edit by hand\n".to_owned();
+
+    for declaration in declarations.output {
+        output.push('\n');
+        output.push_str(&declaration);
+    }
+
+    output
+}
+
+struct Declarations<'a> {
+    options: &'a Options,
+
+    /// the declared names, a type is only declared the first time it's used
+    declared: HashSet<String>,
+
+    /// every declaration, in the order the Rust types are generated
+    output: Vec<String>,
+}
+
+impl Declarations<'_> {
+    fn declare(&mut self, name: &Ident, schema: &JsonSchema) {
+        if !self.declared.insert(name.to_string()) {
+            return;
+        }
+
+        // the nested types come after it, so it's place is taken first
+        let index = self.output.len();
+        self.output.push(String::new());
+
+        let body = match &schema.one_of {
+            Some(variants) => format!(
+                "export type {} = {};\n",
+                name,
+                self.union(name, schema, variants)
+            ),
+            None if matches!(schema.ty, JsonSchemaTypes::Object) && !schema.is_map() => {
+                format!(
+                    "export interface {} {}\n",
+                    name,
+                    self.interface(name, schema)
+                )
+            }
+            None => format!("export type {} = {};\n", name, self.ty(name, "", schema)),
+        };
+
+        self.output[index] = jsdoc(schema.description.as_deref(), "") + &body;
+    }
+
+    // the fields of an interface, between the braces
+    fn interface(&mut self, name: &Ident, schema: &JsonSchema) -> String {
+        let mut fields = String::new();
+
+        for (key, property) in schema.properties.iter().flatten() {
+            // it's left out of the `required`, so it can be missing
+            let optional = schema
+                .required
+                .as_ref()
+                .is_some_and(|required| !required.contains(key));
+
+            fields.push_str(&jsdoc(property.description.as_deref(), "  "));
+            fields.push_str(&format!(
+                "  {}{}: {};\n",
+                self.wire_key(key),
+                if optional { "?" } else { "" },
+                self.ty(name, key, property)
+            ));
+        }
+
+        format!("{{\n{}}}", fields)
+    }
+
+    // the variants of a `oneOf`, named like the variants of the Rust enum
+    fn union(&mut self, name: &Ident, schema: &JsonSchema, variants: &[JsonSchema]) -> String {
+        let tag = schema
+            .discriminator
+            .as_ref()
+            .map(|discriminator| discriminator.property_name.as_str());
+
+        let mut variant_names = HashSet::new();
+        let mut branches = Vec::new();
+
+        for (index, variant) in variants.iter().enumerate() {
+            let mut variant_name = match (
+                variant.struct_name.as_deref().or(variant.title.as_deref()),
+                &variant.ty,
+            ) {
+                (Some(variant_name), _) => type_ident(None, variant_name).to_string(),
+                (None, JsonSchemaTypes::None) => format!("Variant{}", index),
+                (None, ty) => type_ident(None, &ty.to_string()).to_string(),
+            };
+
+            if !variant_names.insert(variant_name.clone()) {
+                variant_name = format!("{}{}", variant_name, index);
+            }
+
+            let branch = match tag {
+                Some(tag) => self.ty(name, &variant_name, &tagged_variant(variant, tag)),
+                None => self.ty(name, &variant_name, variant),
+            };
+
+            branches.push(branch);
+        }
+
+        if branches.is_empty() {
+            branches.push("never".to_owned());
+        }
+
+        if schema.nullable == Some(true) {
+            branches.push("null".to_owned());
+        }
+
+        branches.join(" | ")
+    }
+
+    // the type of a schema, `key` is the property holding it, the nested types are named after it
+    fn ty(&mut self, base_name: &Ident, key: &str, schema: &JsonSchema) -> String {
+        let ty = match (&schema.const_value, &schema.enum_values) {
+            (Some(value), _) => literal(value),
+            (None, Some(values)) if !values.is_empty() => {
+                values.iter().map(literal).collect::<Vec<_>>().join(" | ")
+            }
+            _ if schema.one_of.is_some()
+                || matches!(schema.ty, JsonSchemaTypes::Object) && !schema.is_map() =>
+            {
+                let nested_name = match schema.struct_name.as_deref() {
+                    Some("key") => type_ident(None, key),
+                    Some(struct_name) => type_ident(None, struct_name),
+                    None => type_ident(Some(base_name), key),
+                };
+
+                // being `null` is up to where it's used, like with the Rust types
+                self.declare(
+                    &nested_name,
+                    &JsonSchema {
+                        nullable: None,
+                        ..schema.clone()
+                    },
+                );
+
+                nested_name.to_string()
+            }
+            _ => match &schema.ty {
+                JsonSchemaTypes::Object => {
+                    let values = schema
+                        .additional_properties
+                        .as_deref()
+                        .expect("a map has the schema of it's values");
+
+                    format!("Record<string, {}>", self.ty(base_name, key, values))
+                }
+                JsonSchemaTypes::Array => match schema.items.as_deref() {
+                    Some(items) => {
+                        let items = self.ty(base_name, key, items);
+
+                        match items.contains(' ') {
+                            true => format!("({})[]", items),
+                            false => format!("{}[]", items),
+                        }
+                    }
+                    None => "unknown[]".to_owned(),
+                },
+                JsonSchemaTypes::String => "string".to_owned(),
+                JsonSchemaTypes::Number | JsonSchemaTypes::Integer => "number".to_owned(),
+                JsonSchemaTypes::Boolean => "boolean".to_owned(),
+                JsonSchemaTypes::None => "unknown".to_owned(),
+            },
+        };
+
+        match schema.nullable {
+            Some(true) => format!("{} | null", ty),
+            _ => ty,
+        }
+    }
+
+    // the key as serde writes it, quoted if it's not a valid identifier
+    fn wire_key(&self, key: &str) -> String {
+        let field = field_ident(key);
+
+        let key = match self.options.rename_all {
+            Some(rename_all) if !field.changed => {
+                let field = field.ident.to_string();
+
+                rename_all.apply(field.trim_start_matches("r#"))
+            }
+            _ => key.to_owned(),
+        };
+
+        let mut chars = key.chars();
+        let is_identifier = chars
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+
+        match is_identifier {
+            true => key,
+            false => serde_json::Value::from(key).to_string(),
+        }
+    }
+}
+
+// a variant of a tagged `oneOf`, it's named after the enum, and the tag is the first property
+//
+// the tag is the `const` of the property, or the name of the schema if there's no such property
+fn tagged_variant(variant: &JsonSchema, tag: &str) -> JsonSchema {
+    let mut variant = variant.clone();
+
+    let tag_schema = variant
+        .properties
+        .as_mut()
+        .and_then(|properties| properties.shift_remove(tag))
+        .filter(|property| property.const_value.is_some())
+        .or_else(|| {
+            variant.struct_name.clone().map(|struct_name| JsonSchema {
+                ty: JsonSchemaTypes::String,
+                const_value: Some(JsonSchemaValues::Str(struct_name)),
+                ..Default::default()
+            })
+        });
+
+    if let Some(tag_schema) = tag_schema {
+        let mut properties = IndexMap::from([(tag.to_owned(), tag_schema)]);
+        properties.extend(variant.properties.take().unwrap_or_default());
+
+        variant.properties = Some(properties);
+
+        if let Some(required) = &mut variant.required {
+            if !required.iter().any(|key| key == tag) {
+                required.push(tag.to_owned());
+            }
+        }
+    }
+
+    variant.struct_name = None;
+    variant
+}
+
+// a value as a literal type, like `"admin"` or `42`
+fn literal(value: &JsonSchemaValues) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| "unknown".to_owned())
+}
+
+// the JSDoc of a declaration, indented like it
+fn jsdoc(description: Option<&str>, indent: &str) -> String {
+    let Some(description) = description.map(str::trim).filter(|d| !d.is_empty()) else {
+        return String::new();
+    };
+
+    // the comment can't be closed early
+    let description = description.replace("*/", "*\\/");
+
+    match description.lines().count() {
+        1 => format!("{}/** {} */\n", indent, description),
+        _ => {
+            let lines = description
+                .lines()
+                .map(|line| format!("{} * {}", indent, line).trim_end().to_owned() + "\n")
+                .collect::<String>();
+
+            format!("{}/**\n{}{} */\n", indent, lines, indent)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{files::parse_schema, models::RenameAll};
+
+    fn schema(name: &str, schema: serde_json::Value) -> JsonSchema {
+        let mut schema = parse_schema(schema).unwrap();
+        schema.struct_name = Some(name.to_owned());
+        schema
+    }
+
+    #[test]
+    fn interfaces() {
+        let user = schema(
+            "User",
+            json!({
+                "type": "object",
+                "description": "A user of the app",
+                "properties": {
+                    "name": { "type": "string" },
+                    "role": { "type": "string", "enum": ["admin", "member"] },
+                    "last_seen": { "type": ["string", "null"], "description": "when it was last seen" },
+                    "address": {
+                        "type": "object",
+                        "properties": { "zip": { "type": "string" } }
+                    },
+                    "scores": { "type": "object", "additionalProperties": { "type": "number" } },
+                    "tags": { "type": "array", "items": { "type": "integer" } },
+                    "extra": {}
+                },
+                "required": ["name", "role", "address", "scores", "tags", "extra"]
+            }),
+        );
+
+        let options = Options {
+            rename_all: Some(RenameAll::Camel),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            generate_declarations(&[user], &options),
+            r#"// @generated by schema2struct, do not edit by hand
+
+/** A user of the app */
+export interface User {
+  name: string;
+  role: "admin" | "member";
+  /** when it was last seen */
+  lastSeen?: string | null;
+  address: UserAddress;
+  scores: Record<string, number>;
+  tags: number[];
+  extra: unknown;
+}
+
+export interface UserAddress {
+  zip?: string;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn optional_like_the_rust_fields() {
+        // every property of the macro is required without a `required`, none of a file's is
+        let dsl = crate::parse(quote::quote! {
+            struct: Point,
+            type: object,
+            properties: {
+                "x": { type: number }
+            }
+        })
+        .unwrap();
+        let file = schema(
+            "Size",
+            json!({ "type": "object", "properties": { "width": { "type": "number" } } }),
+        );
+
+        assert_eq!(
+            generate_declarations(&[dsl, file], &Options::default()),
+            r#"// @generated by schema2struct, do not edit by hand
+
+export interface Point {
+  x: number;
+}
+
+export interface Size {
+  width?: number;
+}
+"#
+        );
+    }
+
+    #[test]
+    fn tagged_union() {
+        let mut shape = crate::jtd::lower(&json!({
+            "discriminator": "kind",
+            "mapping": {
+                "circle": { "properties": { "radius": { "type": "float32" } } },
+                "square": { "properties": { "side": { "type": "float32" } } }
+            }
+        }))
+        .ok()
+        .unwrap();
+        shape.struct_name = Some("Shape".to_owned());
+
+        // a type is declared once, however many roots it's in
+        assert_eq!(
+            generate_declarations(&[shape.clone(), shape], &Options::default()),
+            r#"// @generated by schema2struct, do not edit by hand
+
+export type Shape = ShapeCircle | ShapeSquare;
+
+export interface ShapeCircle {
+  kind: "circle";
+  radius: number;
+}
+
+export interface ShapeSquare {
+  kind: "square";
+  side: number;
+}
+"#
+        );
+    }
+}
@@ -0,0 +1,252 @@
+/// # schema2struct: JSON Schema to Rust Struct Generator
+///
+/// A powerful procedural macro for generating Rust structs from JSON Schema definitions
+/// with compile-time type safety
+///
+/// ## Features
+/// - Automatic struct generation from JSON Schema
+/// - Serde integration for easy serialization and deserialization
+/// - Compile-time type checking to catch errors early
+/// - Flexible schema parsing with support for nested structures
+/// - Validation of schema constraints such as required fields, type restrictions, and more
+///
+/// ## Supported Schema Validations
+/// - Type constraints
+/// - Length restrictions
+/// - Numeric ranges
+/// - Required fields
+/// - Array constraints
+///
+/// ## Avaliable keywords
+///    - type => [ object,  string, array, number, integer, boolean]
+///    - title
+///    - required
+///    - description
+///    - items
+///    - properties
+///    - default
+///    - examples
+///    - enum
+///    - const
+///    - min_length
+///    - max_length
+///    - pattern
+///    - format => [date, time, datetime, email, hostname, ipv4, ipv6, uri ]
+///    - minimum
+///    - maximum
+///    - max_items
+///    - min_items
+///    - unique_items
+///    - contains
+///    - struct
+///    - derive => extra derives for every generated type, only in the root schema
+///    - attrs => extra attributes, on every generated type from the root schema and on the field from a property
+///    - vis => the visibility of every generated item, `pub` by default, only in the root schema
+///    - mod => wraps everything in a module and re-exports the root type, only in the root schema
+///    - rename_all => normalizes the keys instead of keeping them as written, only in the root schema
+///    - from_example => infers the properties from one or more example JSON documents, only in the root schema
+///
+/// ## Schema files
+/// a JSON Schema file can be read instead, written in JSON, YAML (`.yaml`, `.yml`) or TOML
+/// (`.toml`) by it's extension, the path is relative to the crate's `Cargo.toml` and a file that
+/// can't be parsed is a compile error with it's line and column
+///
+/// ```ignore
+/// schema2struct!(file = "schemas/user.yaml", derive = [PartialEq]);
+/// ```
+///
+/// a file named like `event.jtd.json` is a JSON Type Definition (RFC 8927) instead, it's
+/// `values` become a `BTreeMap` and a `discriminator` becomes a tagged enum
+///
+/// ```ignore
+/// schema2struct!(file = "schemas/event.jtd.json");
+/// ```
+///
+/// and a `.avsc` file is an Avro schema, it's root `record` becomes the root struct
+///
+/// ```ignore
+/// schema2struct!(file = "schemas/sensor_reading.avsc");
+/// ```
+///
+/// ## Kubernetes
+/// the `openAPIV3Schema` of a `CustomResourceDefinition` manifest can be imported, the `spec` and
+/// the `status` of the resource become `{Kind}Spec` and `{Kind}Status`, from the storage version
+/// if there's no `version`
+///
+/// ```ignore
+/// schema2struct!(crd = "deploy/crontab.yaml", version = "v1");
+/// ```
+///
+/// ## OpenAPI
+/// the components of an OpenAPI 3.0 or 3.1 document can be imported instead, the path is relative
+/// to the crate's `Cargo.toml`, and every component is imported if there's neither `components`
+/// nor `operations`
+///
+/// ```ignore
+/// schema2struct!(openapi = "api.yaml", components = ["User", "Order"], derive = [PartialEq]);
+/// ```
+///
+/// `operations` generates the parameters, the request and the responses of some operations by
+/// their `operationId`, along with the components they use
+///
+/// ```ignore
+/// schema2struct!(openapi = "api.yaml", operations = ["getUser"]);
+///
+/// let response = GetUserResponse::from_response(status, &body)?;
+/// ```
+///
+use schema2struct_core::Options;
+
+/// converts json schema into a useable struct as a response from the schema
+///
+/// # Example
+/// ```ignore
+/// schema2struct! {
+///     struct: User,
+///     type: object,
+///     properties: {
+///         "name": { type: string },
+///         "age": { type: number, minimum: 0 }
+///     },
+///     required: ["name", "age"]
+/// }
+///
+/// fn bind_it() {
+///     let json = &*USER_JSON_VALUE;
+///     let response = // request to an api with the generated json value
+///
+///     let hard_bind_response: User = serde_json::from_str(response.text).unwrap();
+///
+///     // now you can access the fileds using dot notation
+///     println!("{}", hard_bind_response.name);
+/// }
+/// ```
+#[proc_macro]
+pub fn schema2struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    // `openapi = "api.yaml"` imports the components of an OpenAPI document instead
+    if schema2struct_core::openapi::is_openapi_input(&input) {
+        let options = Options {
+            schemars: cfg!(feature = "schemars"),
+            ..Default::default()
+        };
+
+        return schema2struct_core::openapi::expand_openapi(input, &options)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
+
+    // `crd = "crd.yaml"` imports the `spec` and `status` of a Kubernetes custom resource instead
+    if schema2struct_core::kubernetes::is_crd_input(&input) {
+        let options = Options {
+            schemars: cfg!(feature = "schemars"),
+            ..Default::default()
+        };
+
+        return schema2struct_core::kubernetes::expand_crd(input, &options)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
+
+    // `file = "schemas/user.yaml"` reads the schema from a file instead
+    if schema2struct_core::files::is_file_input(&input) {
+        let options = Options {
+            schemars: cfg!(feature = "schemars"),
+            ..Default::default()
+        };
+
+        return schema2struct_core::files::expand_file(input, &options)
+            .unwrap_or_else(|e| e.to_compile_error())
+            .into();
+    }
+
+    // every problem in the schema is reported at once, there's no point in generating from a broken one
+    let schema = match schema2struct_core::parse(input) {
+        Ok(schema) => schema,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let options = Options {
+        schemars: cfg!(feature = "schemars"),
+        ..Options::from(&schema)
+    };
+
+    schema2struct_core::generate(&schema, &options)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Derives `HasJsonSchema` for an existing struct, along with it's `*_JSON_VALUE` constant
+///
+/// The field types become the schema types, and `#[schema(...)]` takes the same keywords as
+/// the `schema2struct!` macro, they're checked against the field type the same way
+///
+/// # Example
+/// ```ignore
+/// use schema2struct::HasJsonSchema;
+///
+/// #[derive(serde::Deserialize, serde::Serialize, HasJsonSchema)]
+/// #[serde(rename_all = "camelCase")]
+/// struct User {
+///     /// the name shown on the profile
+///     #[schema(min_length = 3)]
+///     display_name: String,
+///
+///     #[schema(format = "email")]
+///     email: String,
+///
+///     // any type implementing `HasJsonSchema` is embedded
+///     address: Option<Address>,
+/// }
+///
+/// let json = &*USER_JSON_VALUE;
+/// ```
+#[proc_macro_derive(HasJsonSchema, attributes(schema))]
+pub fn derive_has_json_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let options = Options {
+        schemars: cfg!(feature = "schemars"),
+        ..Default::default()
+    };
+
+    schema2struct_core::derive_has_json_schema(input.into(), &options)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Declares a schema-backed struct in plain Rust, it's re-exported as `schema2struct::attr::schema2struct`
+///
+/// The struct gets the same items as `#[derive(HasJsonSchema)]`, along with the serde derives, and
+/// the `Option` fields are left out when they're `None`
+///
+/// # Example
+/// ```ignore
+/// use schema2struct::attr::schema2struct;
+///
+/// #[schema2struct]
+/// struct Reading {
+///     #[schema(maximum = 90)]
+///     angle: u8,
+///
+///     #[schema(min_length = 1)]
+///     sensor: String,
+///
+///     label: Option<String>,
+/// }
+///
+/// let json = &*READING_JSON_VALUE;
+/// ```
+#[proc_macro_attribute]
+pub fn schema2struct_attr(
+    args: proc_macro::TokenStream,
+    input: proc_macro::TokenStream,
+) -> proc_macro::TokenStream {
+    let options = Options {
+        schemars: cfg!(feature = "schemars"),
+        ..Default::default()
+    };
+
+    schema2struct_core::schema2struct_attr(args.into(), input.into(), &options)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -1,124 +1,217 @@
-/// # schema2struct: JSON Schema to Rust Struct Generator
-///
-/// A powerful procedural macro for generating Rust structs from JSON Schema definitions
-/// with compile-time type safety
-///
-/// ## Features
-/// - Automatic struct generation from JSON Schema
-/// - Serde integration for easy serialization and deserialization
-/// - Compile-time type checking to catch errors early
-/// - Flexible schema parsing with support for nested structures
-/// - Validation of schema constraints such as required fields, type restrictions, and more
-///
-/// ## Supported Schema Validations
-/// - Type constraints
-/// - Length restrictions
-/// - Numeric ranges
-/// - Required fields
-/// - Array constraints
+//! # schema2struct
+//!
+//! Generates Rust structs from JSON Schema definitions, with the [`schema2struct!`] macro
+//! or from schema files with `schema2struct-build` and `schema2struct-cli`
+//!
+//! every generated type implements [`HasJsonSchema`], so generic code can get the schema
+//! of any of them, and it can be derived for hand-written structs with `#[derive(HasJsonSchema)]`
+//!
+//! [`attr::schema2struct`] declares a schema-backed struct in plain Rust syntax instead of the
+//! macro DSL, so it's formatted by rustfmt and completed by the IDE
+//!
+//! # Features
+//! - `schemars`: implements `schemars::JsonSchema` for every generated type, with the schema it
+//!   came from
+//!
+//! # Example
+//! ```ignore
+//! use schema2struct::{schema2struct, HasJsonSchema};
+//!
+//! schema2struct! {
+//!     struct: User,
+//!     type: object,
+//!     properties: {
+//!         "name": { type: string }
+//!     },
+//!     required: ["name"]
+//! }
+//!
+//! fn schema_of<T: HasJsonSchema>() -> &'static serde_json::Value {
+//!     T::json_schema()
+//! }
+//! ```
+pub use schema2struct_macros::{schema2struct, HasJsonSchema};
+
+// the generated `schemars::JsonSchema` implementations go through it, so the version always matches
+#[cfg(feature = "schemars")]
+#[doc(hidden)]
+pub use schemars;
+
+/// A generated type that knows the JSON Schema it came from
 ///
-/// ## Avaliable keywords
-///    - type => [ object,  string, array, number]
-///    - title
-///    - required
-///    - description
-///    - items
-///    - properties
-///    - default
-///    - examples
-///    - enum
-///    - const
-///    - min_length
-///    - max_length
-///    - pattern
-///    - format => [date, time, datetime, email, hostname, ipv4, ipv6, uri ]
-///    - minimum
-///    - maximum
-///    - max_items
-///    - min_items
-///    - unique_items
-///    - contains
-///    - struct
+/// It's implemented for every generated struct, the root and the nested ones, each with it's own
+/// schema as a standalone JSON Schema 2020-12 document
+pub trait HasJsonSchema {
+    /// the schema as a JSON string
+    const SCHEMA_STR: &'static str;
+
+    /// the schema, parsed once on the first call
+    fn json_schema() -> &'static serde_json::Value;
+}
+
+/// An operation imported from an OpenAPI document, it ties together the types generated for it
 ///
-mod checkers;
-mod generator;
-mod models;
-mod parsers;
-mod try_from_impls;
-
-use generator::{generate_structs, JsonMacroInput};
-use models::JsonSchema;
-use proc_macro_error::proc_macro_error;
-use quote::{format_ident, quote};
-
-/// converts json schema into a useable struct as a response from the schema
+/// A generic HTTP client can build any operation's request and decode it's response with it
 ///
-/// # Example
-/// ```rust
-/// schema2struct! {
-///     struct: User,
-///     type: object,
-///     properties: {
-///         "name": { type: string },
-///         "age": { type: number, minimum: 0 }
-///     },
-///     required: ["name", "age"]
+/// ```ignore
+/// fn url<O: Operation>(base: &str) -> String {
+///     format!("{}{}", base, O::PATH)
 /// }
+/// ```
+pub trait Operation {
+    /// the uppercase method, like `GET`
+    const METHOD: &'static str;
+
+    /// the path template, like `/users/{id}`
+    const PATH: &'static str;
+
+    /// the struct of the path parameters, `()` if there's none
+    type PathParams;
+
+    /// the struct of the query parameters, `()` if there's none
+    type QueryParams;
+
+    /// the type of the request body, `()` if there's none, and an `Option` if it's not `required`
+    type Request;
+
+    /// the enum over the responses, with a variant for each status
+    type Response;
+
+    /// Decodes a response by it's status
+    ///
+    /// # Errors
+    /// - The status isn't one of the operation's responses
+    /// - The body doesn't match the schema of it's status
+    fn decode_response(status: u16, body: &[u8]) -> Result<Self::Response, ResponseError>;
+}
+
+/// Why a response of an [`Operation`] can't be decoded
+#[derive(Debug)]
+pub enum ResponseError {
+    /// the status isn't one of the operation's responses, and it has no `default` one
+    UnexpectedStatus(u16),
+
+    /// the body doesn't match the schema of it's status
+    Body(serde_json::Error),
+}
+
+impl std::fmt::Display for ResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnexpectedStatus(status) => write!(f, "unexpected status {}", status),
+            Self::Body(e) => write!(f, "invalid response body: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ResponseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnexpectedStatus(_) => None,
+            Self::Body(e) => Some(e),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ResponseError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Body(e)
+    }
+}
+
+/// The attribute form of [`schema2struct!`], for structs written in plain Rust
 ///
-/// fn bind_it() {
-///     let json = &*USER_JSON_VALUE;
-///     let response = // request to an api with the generated json value
+/// It can't share the name of the function-like macro at the root of the crate, so it lives here
 ///
-///     let hard_bind_response: User = serde_json::from_str(response.text).unwrap();
+/// ```ignore
+/// use schema2struct::attr::schema2struct;
 ///
-///     // now you can access the fileds using dot notation
-///     println!("{}", hard_bind_response.name);
+/// #[schema2struct]
+/// struct Reading {
+///     #[schema(maximum = 90)]
+///     angle: u8,
 /// }
 /// ```
-#[proc_macro_error]
-#[proc_macro]
-pub fn schema2struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    let schema = syn::parse_macro_input!(input as JsonSchema);
+pub mod attr {
+    pub use schema2struct_macros::schema2struct_attr as schema2struct;
+}
 
-    if let Some(struct_name) = &schema.struct_name {
-        let title = format_ident!("{}", struct_name);
+// used by the code of `#[derive(HasJsonSchema)]`, a struct's schema string is put together at compile
+// time from the `SCHEMA_STR` of it's field types
+#[doc(hidden)]
+pub mod __private {
+    const DIALECT_HEADER: &[u8] = br#"{"$schema":"https://json-schema.org/draft/2020-12/schema","#;
 
-        let json = schema.to_json_sample();
+    pub enum Part {
+        Str(&'static str),
 
-        let json_struct = &JsonMacroInput {
-            struct_name: title.clone(),
-            content: json,
-        };
+        // the schema of a field type, it's embedded without it's `$schema`
+        Schema(&'static str),
+    }
 
-        let mut output = proc_macro2::TokenStream::new();
+    const fn bytes_of(part: &Part) -> (&'static [u8], &'static [u8]) {
+        match part {
+            Part::Str(str) => (b"", str.as_bytes()),
+            Part::Schema(schema) => {
+                let schema = schema.as_bytes();
 
-        let (main_struct, other_nested_struct) = generate_structs(json_struct, &title);
+                if schema.len() < DIALECT_HEADER.len() {
+                    return (b"", schema);
+                }
 
-        output.extend(get_serde_const(&schema, &title));
+                let (header, rest) = schema.split_at(DIALECT_HEADER.len());
 
-        output.extend(quote! {
-            #main_struct
-            #(#other_nested_struct)*
-        });
+                let mut i = 0;
+                while i < header.len() {
+                    if header[i] != DIALECT_HEADER[i] {
+                        return (b"", schema);
+                    }
+                    i += 1;
+                }
 
-        return output.into();
+                (b"{", rest)
+            }
+        }
     }
 
-    proc_macro::TokenStream::new()
-}
+    pub const fn len(parts: &[Part]) -> usize {
+        let mut len = 0;
+
+        let mut i = 0;
+        while i < parts.len() {
+            let (prefix, rest) = bytes_of(&parts[i]);
+            len += prefix.len() + rest.len();
+            i += 1;
+        }
+
+        len
+    }
+
+    pub const fn concat<const N: usize>(parts: &[Part]) -> [u8; N] {
+        let mut bytes = [0; N];
+        let mut position = 0;
+
+        let mut i = 0;
+        while i < parts.len() {
+            let (prefix, rest) = bytes_of(&parts[i]);
+
+            let mut j = 0;
+            while j < prefix.len() {
+                bytes[position] = prefix[j];
+                position += 1;
+                j += 1;
+            }
 
-// gets the whole schema as json and save it to a const value
-fn get_serde_const(schema: &JsonSchema, title: &syn::Ident) -> proc_macro2::TokenStream {
-    let serde_value_str = serde_json::to_string(schema).unwrap_or_default();
+            let mut j = 0;
+            while j < rest.len() {
+                bytes[position] = rest[j];
+                position += 1;
+                j += 1;
+            }
 
-    // Generate a constant name based on struct name
-    let const_json_ident = format_ident!("{}_{}", title.to_string().to_uppercase(), "JSON_VALUE");
+            i += 1;
+        }
 
-    quote! {
-        pub static #const_json_ident: ::std::sync::LazyLock<::serde_json::Value> =
-            ::std::sync::LazyLock::new(||
-                ::serde_json::from_str(#serde_value_str)
-                    .expect("Couldn't convert the text into valid json")
-            );
+        bytes
     }
 }
@@ -0,0 +1,80 @@
+use schema2struct::{attr::schema2struct, HasJsonSchema};
+use serde_json::json;
+
+#[schema2struct]
+#[derive(Debug, PartialEq)]
+struct Reading {
+    #[schema(maximum = 90)]
+    angle: u8,
+
+    label: Option<String>,
+
+    #[serde(default)]
+    note: Option<String>,
+}
+
+#[schema2struct]
+#[derive(Debug, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Batch {
+    batch_id: String,
+    readings: Vec<Reading>,
+}
+
+#[test]
+fn the_serde_derives_are_added() {
+    let batch: Batch =
+        serde_json::from_str(r#"{ "batchId": "b-1", "readings": [{ "angle": 45 }] }"#).unwrap();
+
+    assert_eq!(batch.batch_id, "b-1");
+    assert_eq!(
+        batch.readings,
+        [Reading {
+            angle: 45,
+            label: None,
+            note: None
+        }]
+    );
+
+    // `Serialize` is added even though `Deserialize` was already derived
+    assert_eq!(
+        serde_json::to_value(&batch.readings[0]).unwrap(),
+        json!({ "angle": 45, "note": null })
+    );
+}
+
+#[test]
+fn a_missing_option_is_left_out() {
+    let reading = Reading {
+        angle: 10,
+        label: Some("north".to_owned()),
+        note: Some("windy".to_owned()),
+    };
+
+    assert_eq!(
+        serde_json::to_value(&reading).unwrap(),
+        json!({ "angle": 10, "label": "north", "note": "windy" })
+    );
+}
+
+#[test]
+fn the_schema_is_derived_too() {
+    assert_eq!(
+        *READING_JSON_VALUE,
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Reading",
+            "type": "object",
+            "properties": {
+                "angle": { "type": "integer", "minimum": 0, "maximum": 90 },
+                "label": { "type": ["string", "null"] },
+                "note": { "type": ["string", "null"] }
+            },
+            "required": ["angle"]
+        })
+    );
+    assert_eq!(
+        Batch::json_schema()["properties"]["readings"]["items"]["required"],
+        json!(["angle"])
+    );
+}
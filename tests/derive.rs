@@ -0,0 +1,117 @@
+use std::collections::{BTreeMap, HashMap};
+
+use schema2struct::HasJsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Deserialize, Serialize, HasJsonSchema)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[derive(Deserialize, Serialize, HasJsonSchema)]
+struct Shape {
+    /// the corners, in order
+    corners: Vec<Point>,
+
+    #[schema(title = "Center")]
+    center: Point,
+
+    /// where it was moved from
+    origin: Option<Point>,
+
+    anchor: Option<Point>,
+    named: HashMap<String, Point>,
+    tags: BTreeMap<String, Option<String>>,
+    extra: HashMap<String, serde_json::Value>,
+
+    #[serde(rename = "bounds/min")]
+    min: Box<Point>,
+}
+
+fn point() -> serde_json::Value {
+    json!({
+        "title": "Point",
+        "type": "object",
+        "properties": {
+            "x": { "type": "number" },
+            "y": { "type": "number" }
+        },
+        "required": ["x", "y"]
+    })
+}
+
+#[test]
+fn nested_types_are_embedded_where_they_are_used() {
+    assert_eq!(
+        *Shape::json_schema(),
+        json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Shape",
+            "type": "object",
+            "properties": {
+                "corners": {
+                    "type": "array",
+                    "description": "the corners, in order",
+                    "items": point()
+                },
+                "center": { "title": "Center", "allOf": [point()] },
+                "origin": {
+                    "description": "where it was moved from",
+                    "anyOf": [point(), { "type": "null" }]
+                },
+                "anchor": { "anyOf": [point(), { "type": "null" }] },
+                "named": { "type": "object", "additionalProperties": point() },
+                "tags": {
+                    "type": "object",
+                    "additionalProperties": { "type": ["string", "null"] }
+                },
+                "extra": { "type": "object", "additionalProperties": {} },
+                "bounds/min": point()
+            },
+            "required": ["corners", "center", "named", "tags", "extra", "bounds/min"]
+        })
+    );
+}
+
+#[test]
+fn the_schema_is_the_json_value() {
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(Shape::SCHEMA_STR).unwrap(),
+        *SHAPE_JSON_VALUE
+    );
+    assert_eq!(*POINT_JSON_VALUE, *Point::json_schema());
+}
+
+#[derive(Deserialize, Serialize, HasJsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct Account {
+    #[schema(min_length = 3, format = "email")]
+    email_address: String,
+
+    #[serde(rename = "id")]
+    account_id: u64,
+
+    #[serde(default)]
+    retries: u8,
+
+    #[serde(skip)]
+    #[allow(dead_code)]
+    session: String,
+}
+
+#[test]
+fn serde_attributes_are_followed() {
+    let schema = Account::json_schema();
+
+    assert_eq!(
+        schema["properties"],
+        json!({
+            "emailAddress": { "type": "string", "minLength": 3, "format": "email" },
+            "id": { "type": "integer", "minimum": 0 },
+            "retries": { "type": "integer", "minimum": 0 }
+        })
+    );
+    assert_eq!(schema["required"], json!(["emailAddress", "id"]));
+}
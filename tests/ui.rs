@@ -0,0 +1,5 @@
+// the schema errors are reported at the right spans, and all of them at once
+#[test]
+fn ui() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
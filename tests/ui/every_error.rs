@@ -0,0 +1,14 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: User,
+    type: object,
+    properties: {
+        "name": { type: string }
+        "age": { type: integer, minimum: 10, maximum: 2 },
+        12: { type: string },
+        "email": { type: strng },
+    },
+}
+
+fn main() {}
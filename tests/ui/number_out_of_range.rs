@@ -0,0 +1,11 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: Reading,
+    type: object,
+    properties: {
+        "celsius": { type: number, minimum: -40, maximum: 99999999999999999999 },
+    },
+}
+
+fn main() {}
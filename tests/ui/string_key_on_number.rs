@@ -0,0 +1,12 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: Price,
+    type: object,
+    properties: {
+        "amount": { type: number, min_length: 2, format: email },
+        "version": { type: integer, const: 1.5 },
+    },
+}
+
+fn main() {}
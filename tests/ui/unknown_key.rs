@@ -0,0 +1,11 @@
+use schema2struct::schema2struct;
+
+schema2struct! {
+    struct: User,
+    type: object,
+    propertes: {
+        "name": { type: string },
+    },
+}
+
+fn main() {}